@@ -0,0 +1,157 @@
+use std::fmt;
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+/// What sort of failure an error is, so a runner can tell a bad input from a
+/// puzzle with no answer without knowing every crate's error type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Io,
+    Parse,
+    InvalidInput,
+    NoSolution,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Io => "io error",
+            Self::Parse => "parse error",
+            Self::InvalidInput => "invalid input",
+            Self::NoSolution => "no solution",
+        })
+    }
+}
+
+/// Implemented by every puzzle's error type
+pub trait Classify {
+    fn kind(&self) -> ErrorKind;
+}
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("failed to parse input: {message}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError {
+        message: String,
+        #[source_code]
+        src: String,
+        #[label("here")]
+        span: SourceSpan,
+    },
+
+    #[error("invalid input: {0}")]
+    #[diagnostic(code(aoc::invalid_input))]
+    InvalidInput(String),
+
+    #[error("no solution: {0}")]
+    #[diagnostic(code(aoc::no_solution))]
+    NoSolution(String),
+}
+
+impl AocError {
+    pub fn parse(src: &str, span: impl Into<SourceSpan>, message: impl Into<String>) -> Self {
+        Self::ParseError {
+            message: message.into(),
+            src: src.to_string(),
+            span: span.into(),
+        }
+    }
+
+    /// Points at `part`, a slice of `src` like a line or a word split off it. Anything
+    /// else gets pointed at the start.
+    pub fn parse_at(src: &str, part: &str, message: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize).wrapping_sub(src.as_ptr() as usize);
+        let span = if offset + part.len() <= src.len() { (offset, part.len()) } else { (0, 0) };
+
+        Self::parse(src, span, message)
+    }
+
+    /// Points at wherever in `src` the parser gave up. nom only hands back the
+    /// input that was left, so that has to be a suffix of `src`.
+    pub fn from_nom(src: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                let offset = src.len().saturating_sub(error.input.len());
+                let length = error.input.chars().next().map_or(0, char::len_utf8);
+
+                Self::parse(
+                    src,
+                    (offset, length),
+                    format!("expected {}", error.code.description()),
+                )
+            }
+            nom::Err::Incomplete(_) => Self::parse(src, (src.len(), 0), "incomplete input"),
+        }
+    }
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::IoError(_) => ErrorKind::Io,
+            Self::ParseError { .. } => ErrorKind::Parse,
+            Self::InvalidInput(_) => ErrorKind::InvalidInput,
+            Self::NoSolution(_) => ErrorKind::NoSolution,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{character::complete::u32, IResult};
+    use rstest::rstest;
+
+    fn number(input: &str) -> IResult<&str, u32> {
+        u32(input)
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let src = "12\nab";
+        let error = number(&src[3..]).unwrap_err();
+
+        let AocError::ParseError { span, message, .. } = AocError::from_nom(src, error) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(3, span.offset());
+        assert_eq!(1, span.len());
+        assert_eq!("expected Digit", message);
+    }
+
+    #[test]
+    fn test_parse_at() {
+        let src = "1 2\n3 x";
+        let word = src.lines().nth(1).unwrap().split_whitespace().nth(1).unwrap();
+
+        let AocError::ParseError { span, .. } = AocError::parse_at(src, word, "not a number") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((6, 1), (span.offset(), span.len()));
+
+        let AocError::ParseError { span, .. } = AocError::parse_at(src, "x", "not a number") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((0, 0), (span.offset(), span.len()));
+    }
+
+    #[rstest]
+    #[case(AocError::from(std::io::Error::other("disk")), ErrorKind::Io)]
+    #[case(AocError::parse("x", (0, 1), "not a number"), ErrorKind::Parse)]
+    #[case(AocError::InvalidInput("no start".to_string()), ErrorKind::InvalidInput)]
+    #[case(AocError::NoSolution("stuck".to_string()), ErrorKind::NoSolution)]
+    fn test_kind(#[case] error: AocError, #[case] expected: ErrorKind) {
+        assert_eq!(expected, error.kind());
+        assert!(error.code().is_some());
+    }
+}
@@ -0,0 +1,72 @@
+//! Made up inputs for benches and tests, behind the `testing` feature
+
+/// Plenty of made up numbers, the same ones every run for the same seed so
+/// benches compare like with like and failures reproduce
+#[derive(Debug, Clone)]
+pub struct Numbers(u64);
+
+impl Numbers {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves zero
+        Self(if seed == 0 { 0x2024 } else { seed })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        // xorshift64*
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Somewhere in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Somewhere in `min..=max`
+    pub fn between(&mut self, min: i128, max: i128) -> i128 {
+        min + (self.next_u64() as i128).rem_euclid(max - min + 1)
+    }
+
+    /// Fisher-Yates, in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let (mut a, mut b) = (Numbers::new(7), Numbers::new(7));
+
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Numbers::new(7).next_u64(), Numbers::new(8).next_u64());
+        assert_ne!(0, Numbers::new(0).next_u64());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut numbers = Numbers::new(1);
+
+        for _ in 0..1000 {
+            assert!(numbers.below(6) < 6);
+            assert!((-3..=3).contains(&numbers.between(-3, 3)));
+        }
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut items = (0..50).collect::<Vec<_>>();
+        Numbers::new(3).shuffle(&mut items);
+
+        assert_ne!((0..50).collect::<Vec<_>>(), items);
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<_>>(), items);
+    }
+}
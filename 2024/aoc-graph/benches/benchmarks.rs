@@ -0,0 +1,199 @@
+//! The crate's searches next to the hand written loops days 16 and 18 used to have,
+//! on a 71x71 grid (day 18's size) with a scattering of walls
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+};
+
+fn main() {
+    divan::main();
+}
+
+const SIZE: i32 = 71;
+const GOAL: (i32, i32) = (SIZE - 1, SIZE - 1);
+/// Up, Right, Down, Left
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+fn walls() -> HashSet<(i32, i32)> {
+    (0..SIZE)
+        .flat_map(|x| (0..SIZE).map(move |y| (x, y)))
+        .filter(|&(x, y)| (x * 7 + y * 13) % 5 == 0 && (x + y) % 3 != 0)
+        .filter(|&pos| pos != (0, 0) && pos != GOAL)
+        .collect()
+}
+
+fn open(walls: &HashSet<(i32, i32)>, (x, y): (i32, i32)) -> bool {
+    x >= 0 && y >= 0 && x < SIZE && y < SIZE && !walls.contains(&(x, y))
+}
+
+fn steps(walls: &HashSet<(i32, i32)>, (x, y): (i32, i32)) -> impl Iterator<Item = (i32, i32)> + '_ {
+    DIRECTIONS
+        .into_iter()
+        .map(move |(dx, dy)| (x + dx, y + dy))
+        .filter(|&pos| open(walls, pos))
+}
+
+/// Moving on costs 1 and turning on the spot 1000, like the reindeer maze
+fn reindeer(
+    walls: &HashSet<(i32, i32)>,
+    &(pos, dir): &((i32, i32), usize),
+) -> impl Iterator<Item = (((i32, i32), usize), usize)> {
+    let ahead = (pos.0 + DIRECTIONS[dir].0, pos.1 + DIRECTIONS[dir].1);
+
+    [
+        open(walls, ahead).then_some(((ahead, dir), 1)),
+        Some(((pos, (dir + 1) % 4), 1000)),
+        Some(((pos, (dir + 3) % 4), 1000)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+mod bfs {
+    use super::*;
+
+    #[divan::bench]
+    fn crate_bfs(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| aoc_graph::bfs((0, 0), |&pos| steps(&walls, pos), |&pos| pos == GOAL).map(|path| path.len()));
+    }
+
+    #[divan::bench]
+    fn crate_bfs_len(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| aoc_graph::bfs_len((0, 0), |&pos| steps(&walls, pos), |&pos| pos == GOAL));
+    }
+
+    #[divan::bench]
+    fn crate_bidirectional(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| {
+            aoc_graph::bidirectional_bfs((0, 0), GOAL, |&pos| steps(&walls, pos), |&pos| steps(&walls, pos))
+                .map(|path| path.len())
+        });
+    }
+
+    /// day 18's old `bfs`, which only counted the steps like `crate_bfs_len`
+    #[divan::bench]
+    fn hand_rolled(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| {
+            let mut todo = VecDeque::from([((0, 0), 0)]);
+            let mut seen = HashSet::from([(0, 0)]);
+
+            while let Some((pos, cost)) = todo.pop_front() {
+                if pos == GOAL {
+                    return Some(cost);
+                }
+                for next in steps(&walls, pos) {
+                    if seen.insert(next) {
+                        todo.push_back((next, cost + 1));
+                    }
+                }
+            }
+            None
+        });
+    }
+
+    /// The same with a map of where each position was reached from, to walk back
+    /// along for the path like `crate_bfs` hands out
+    #[divan::bench]
+    fn hand_rolled_path(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| {
+            let mut todo = VecDeque::from([(0, 0)]);
+            let mut parents = HashMap::from([((0, 0), (0, 0))]);
+
+            while let Some(pos) = todo.pop_front() {
+                if pos == GOAL {
+                    let mut path = vec![pos];
+                    while path[path.len() - 1] != (0, 0) {
+                        path.push(parents[&path[path.len() - 1]]);
+                    }
+                    path.reverse();
+                    return Some(path.len());
+                }
+                for next in steps(&walls, pos) {
+                    if let Entry::Vacant(entry) = parents.entry(next) {
+                        entry.insert(pos);
+                        todo.push_back(next);
+                    }
+                }
+            }
+            None
+        });
+    }
+}
+
+mod dijkstra {
+    use super::*;
+
+    #[divan::bench]
+    fn crate_dijkstra(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| {
+            aoc_graph::dijkstra(((0, 0), 1), |state| reindeer(&walls, state), |state| state.0 == GOAL).map(|(_, cost)| cost)
+        });
+    }
+
+    #[divan::bench]
+    fn crate_astar(bencher: divan::Bencher) {
+        let walls = walls();
+        let manhattan = |&((x, y), _): &((i32, i32), usize)| (GOAL.0 - x + GOAL.1 - y) as usize;
+        bencher.bench(|| {
+            aoc_graph::astar(((0, 0), 1), |state| reindeer(&walls, state), manhattan, |state| state.0 == GOAL)
+                .map(|(_, cost)| cost)
+        });
+    }
+
+    #[divan::bench]
+    fn crate_dijkstra_all(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| {
+            aoc_graph::dijkstra_all(((0, 0), 1), |state| reindeer(&walls, state), |state| state.0 == GOAL).goal_cost()
+        });
+    }
+
+    #[derive(PartialEq, Eq)]
+    struct State {
+        position: (i32, i32),
+        direction: usize,
+        cost: usize,
+    }
+
+    impl Ord for State {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.cost.cmp(&self.cost)
+        }
+    }
+
+    impl PartialOrd for State {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    /// day 16's old part 1 loop
+    #[divan::bench]
+    fn hand_rolled(bencher: divan::Bencher) {
+        let walls = walls();
+        bencher.bench(|| {
+            let mut queue = BinaryHeap::from([State { position: (0, 0), direction: 1, cost: 0 }]);
+            let mut visited = HashSet::new();
+
+            while let Some(current) = queue.pop() {
+                if current.position == GOAL {
+                    return Some(current.cost);
+                }
+                if !visited.insert((current.position, current.direction)) {
+                    continue;
+                }
+                for ((position, direction), step) in reindeer(&walls, &(current.position, current.direction)) {
+                    queue.push(State { position, direction, cost: current.cost + step });
+                }
+            }
+            None
+        });
+    }
+}
@@ -0,0 +1,303 @@
+use std::{collections::VecDeque, hash::Hash, ops::Range};
+
+use crate::{hash::StateSet, Visited};
+
+/// Fewest steps from `start` to the first state `goal` accepts, both ends included.
+///
+/// States are numbered in the order they're found, which is already the order a
+/// queue would hand them out, so there's no separate queue.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    if goal(&start) {
+        return Some(vec![start]);
+    }
+
+    let mut visited = Visited::new(start);
+    let mut current = 0;
+    while current < visited.len() {
+        for next in neighbors(visited.node(current)) {
+            if let Some(idx) = visited.insert(next, current) {
+                if goal(visited.node(idx)) {
+                    return Some(visited.path(idx));
+                }
+            }
+        }
+        current += 1;
+    }
+
+    None
+}
+
+/// How many steps [`bfs`] would take, without keeping track of the way there
+pub fn bfs_len<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    if goal(&start) {
+        return Some(0);
+    }
+
+    let mut seen = StateSet::from_iter([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, steps)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                if goal(&next) {
+                    return Some(steps + 1);
+                }
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Everything reachable from `start` with how many steps it takes, nearest first
+pub fn bfs_reach<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Vec<(N, usize)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new(start);
+    let mut steps = vec![0];
+    let mut current = 0;
+    while current < visited.len() {
+        for next in neighbors(visited.node(current)) {
+            if visited.insert(next, current).is_some() {
+                steps.push(steps[current] + 1);
+            }
+        }
+        current += 1;
+    }
+
+    visited.nodes.into_iter().zip(steps).collect()
+}
+
+/// Marks the states [`bfs_reach_dense`] never got to
+pub const UNREACHED: u32 = u32::MAX;
+
+/// What [`bfs_reach_dense`] found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseReach<N> {
+    /// the states reached, nearest first
+    pub order: Vec<N>,
+    /// steps to every state by its number, [`UNREACHED`] where there's no way
+    pub steps: Vec<u32>,
+}
+
+/// [`bfs_reach`] for states that `index` can number `0..len`, like the cells of a
+/// flat grid, so a table stands in for hashing them
+pub fn bfs_reach_dense<N, I>(
+    start: N,
+    len: usize,
+    index: impl Fn(&N) -> usize,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> DenseReach<N>
+where
+    I: IntoIterator<Item = N>,
+{
+    let mut steps = vec![UNREACHED; len];
+    steps[index(&start)] = 0;
+    let mut order = vec![start];
+
+    // the order they're found in doubles as the queue
+    let mut current = 0;
+    while let Some(node) = order.get(current) {
+        let next_steps = steps[index(node)] + 1;
+        for next in neighbors(node) {
+            let idx = index(&next);
+            if steps[idx] == UNREACHED {
+                steps[idx] = next_steps;
+                order.push(next);
+            }
+        }
+        current += 1;
+    }
+
+    DenseReach { order, steps }
+}
+
+/// Fewest steps from `start` to `goal`, searching from both ends at once and
+/// growing whichever frontier is smaller.
+///
+/// `predecessors` gives the states one step *before* a state, which for undirected
+/// graphs like open grids is just `neighbors` again.
+pub fn bidirectional_bfs<N, I, J>(
+    start: N,
+    goal: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut predecessors: impl FnMut(&N) -> J,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    J: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(vec![start]);
+    }
+
+    let mut forward = Visited::new(start);
+    let mut backward = Visited::new(goal);
+    let (mut forward_layer, mut backward_layer) = (0..1, 0..1);
+
+    // Nothing has met while the frontiers are `a` and `b` steps out, so the path is
+    // longer than `a + b` and the first meeting has to be one of the shortest
+    let (forward_idx, backward_idx) = loop {
+        if forward_layer.is_empty() || backward_layer.is_empty() {
+            return None;
+        }
+
+        if forward_layer.len() <= backward_layer.len() {
+            match grow(&mut forward, forward_layer, &backward, &mut neighbors) {
+                Ok(layer) => forward_layer = layer,
+                Err(met) => break met,
+            }
+        } else {
+            match grow(&mut backward, backward_layer, &forward, &mut predecessors) {
+                Ok(layer) => backward_layer = layer,
+                Err((backward_idx, forward_idx)) => break (forward_idx, backward_idx),
+            }
+        }
+    };
+
+    let mut path = forward.path(forward_idx);
+    path.extend(backward.path(backward_idx).into_iter().rev().skip(1));
+    Some(path)
+}
+
+/// Takes one more step from every state in `layer`, handing back the next layer,
+/// or where this side ran into `other`
+fn grow<N, I>(
+    side: &mut Visited<N>,
+    layer: Range<usize>,
+    other: &Visited<N>,
+    neighbors: &mut impl FnMut(&N) -> I,
+) -> Result<Range<usize>, (usize, usize)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let end = side.len();
+    for current in layer {
+        for next in neighbors(side.node(current)) {
+            if let Some(idx) = side.insert(next, current) {
+                if let Some(met) = other.get(side.node(idx)) {
+                    return Err((idx, met));
+                }
+            }
+        }
+    }
+
+    Ok(end..side.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::tests::{grid, CORRUPTED};
+
+    #[test]
+    fn test_corrupted_memory() {
+        let neighbors = grid(7, 7, |x, y| CORRUPTED.contains(&(x, y)));
+
+        let path = bfs((0, 0), &neighbors, |&pos| pos == (6, 6)).unwrap();
+        assert_eq!(22, path.len() - 1);
+        assert_eq!((0, 0), path[0]);
+        assert!(path.windows(2).all(|step| neighbors(&step[0]).contains(&step[1])));
+
+        let both_ways = bidirectional_bfs((0, 0), (6, 6), &neighbors, &neighbors).unwrap();
+        assert_eq!(path.len(), both_ways.len());
+        assert_eq!(Some(&(6, 6)), both_ways.last());
+        assert!(both_ways.windows(2).all(|step| neighbors(&step[0]).contains(&step[1])));
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(2, 3)]
+    #[case(9, 4)]
+    #[case(31, 17)]
+    fn test_both_ways_agree(#[case] width: i32, #[case] height: i32) {
+        // a scattering of walls that leaves some corners cut off
+        let blocked = |x: i32, y: i32| (x * 7 + y * 13) % 5 == 0 && (x, y) != (0, 0);
+        let neighbors = grid(width, height, blocked);
+        let reach = bfs_reach((0, 0), &neighbors);
+
+        for &(goal, steps) in &reach {
+            let one_way = bfs((0, 0), &neighbors, |&pos| pos == goal).unwrap();
+            let both_ways = bidirectional_bfs((0, 0), goal, &neighbors, &neighbors).unwrap();
+
+            assert_eq!(steps + 1, one_way.len());
+            assert_eq!(steps + 1, both_ways.len());
+            assert_eq!(Some(steps), bfs_len((0, 0), &neighbors, |&pos| pos == goal));
+        }
+
+        let unreachable = (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .filter(|&(x, y)| !blocked(x, y))
+            .find(|pos| !reach.iter().any(|(reached, _)| reached == pos));
+        if let Some(goal) = unreachable {
+            assert_eq!(None, bfs((0, 0), &neighbors, |&pos| pos == goal));
+            assert_eq!(None, bfs_len((0, 0), &neighbors, |&pos| pos == goal));
+            assert_eq!(None, bidirectional_bfs((0, 0), goal, &neighbors, &neighbors));
+        }
+    }
+
+    #[test]
+    fn test_reach_is_nearest_first() {
+        let reach = bfs_reach(0_u32, |&n| [n * 2, n + 1].into_iter().filter(|&n| n <= 10));
+
+        assert_eq!((0, 0), reach[0]);
+        assert_eq!(11, reach.len());
+        assert!(reach.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert!(reach.contains(&(10, 5)));
+    }
+
+    #[test]
+    fn test_dense_matches_hashed() {
+        let neighbors = grid(9, 4, |x, y| (x * 7 + y * 13) % 5 == 0 && (x, y) != (0, 0));
+        let number = |(x, y): (i32, i32)| (y * 9 + x) as usize;
+
+        let hashed = bfs_reach((0, 0), &neighbors);
+        let dense = bfs_reach_dense((0, 0), 36, |&pos| number(pos), &neighbors);
+
+        assert_eq!(hashed.iter().map(|&(pos, _)| pos).collect::<Vec<_>>(), dense.order);
+        for (pos, steps) in hashed {
+            assert_eq!(steps as u32, dense.steps[number(pos)]);
+        }
+        assert_eq!(
+            36 - dense.order.len(),
+            dense.steps.iter().filter(|&&steps| steps == UNREACHED).count()
+        );
+    }
+
+    #[test]
+    fn test_one_way_streets() {
+        // 0 -> 1 -> 2 -> 3 and no way back
+        let neighbors = |&n: &u8| if n < 3 { vec![n + 1] } else { vec![] };
+        let predecessors = |&n: &u8| if n > 0 { vec![n - 1] } else { vec![] };
+
+        assert_eq!(
+            Some(vec![0, 1, 2, 3]),
+            bidirectional_bfs(0, 3, neighbors, predecessors)
+        );
+        assert_eq!(None, bidirectional_bfs(3, 0, neighbors, predecessors));
+        assert_eq!(Some(vec![2]), bidirectional_bfs(2, 2, neighbors, predecessors));
+    }
+}
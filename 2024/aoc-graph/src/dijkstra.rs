@@ -0,0 +1,130 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap},
+    hash::Hash,
+};
+
+use crate::{Cost, Visited};
+
+/// The cheapest path from `start` to the first state `goal` accepts, and what it costs
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), goal)
+}
+
+/// Like [`dijkstra`], trying the states `heuristic` thinks are closest to the goal
+/// first. It mustn't ever guess more than the real remaining cost, or the path
+/// found might not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let zero = C::default();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut visited = Visited::new(start);
+    let mut costs = vec![zero];
+
+    while let Some(Reverse((_, cost, current))) = heap.pop() {
+        // a cheaper way here was found after this one was queued
+        if cost > costs[current] {
+            continue;
+        }
+        if goal(visited.node(current)) {
+            return Some((visited.path(current), cost));
+        }
+
+        for (next, step) in neighbors(visited.node(current)) {
+            let next_cost = cost + step;
+            let idx = match visited.index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let idx = *entry.get();
+                    if next_cost >= costs[idx] {
+                        continue;
+                    }
+                    costs[idx] = next_cost;
+                    visited.parents[idx] = current;
+                    idx
+                }
+                Entry::Vacant(entry) => {
+                    let idx = visited.nodes.len();
+                    visited.nodes.push(entry.key().clone());
+                    visited.parents.push(current);
+                    costs.push(next_cost);
+                    entry.insert(idx);
+                    idx
+                }
+            };
+            heap.push(Reverse((next_cost + heuristic(visited.node(idx)), next_cost, idx)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::tests::{grid, CORRUPTED};
+
+    /// a -1- b -1- c -1- d, with a -5-> d directly and c -1-> a back
+    fn roads(&node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 5)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1), ('a', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[rstest]
+    #[case('d', Some((vec!['a', 'b', 'c', 'd'], 3)))]
+    #[case('a', Some((vec!['a'], 0)))]
+    #[case('e', None)]
+    fn test_cheapest_not_shortest(#[case] goal: char, #[case] expected: Option<(Vec<char>, u32)>) {
+        assert_eq!(expected, dijkstra('a', roads, |&node| node == goal));
+        assert_eq!(expected, astar('a', roads, |_| 0, |&node| node == goal));
+    }
+
+    #[test]
+    fn test_turns_cost_more() {
+        // walking east or west is twice as cheap as north or south
+        let neighbors = grid(7, 7, |x, y| CORRUPTED.contains(&(x, y)));
+        let weighted = |pos: &(i32, i32)| {
+            neighbors(pos)
+                .into_iter()
+                .map(|next| (next, if next.1 == pos.1 { 1 } else { 2 }))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (6 - x) + (6 - y);
+
+        let (path, cost) = dijkstra((0, 0), weighted, |&pos| pos == (6, 6)).unwrap();
+        let (_, guessed) = astar((0, 0), weighted, manhattan, |&pos| pos == (6, 6)).unwrap();
+
+        assert_eq!(cost, guessed);
+        assert_eq!(
+            cost,
+            path.windows(2)
+                .map(|step| if step[0].1 == step[1].1 { 1 } else { 2 })
+                .sum::<i32>()
+        );
+        assert!(cost > 22);
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasherDefault, Hasher},
+};
+
+/// What the searches number their states with. Nobody picks puzzle states to
+/// collide, so a multiply and a rotate per word will do instead of SipHash, which
+/// was most of the time spent looking states up.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct StateHasher(u64);
+
+pub(crate) type StateMap<K, V> = HashMap<K, V, BuildHasherDefault<StateHasher>>;
+pub(crate) type StateSet<K> = HashSet<K, BuildHasherDefault<StateHasher>>;
+
+impl StateHasher {
+    /// the constant rustc's own FxHasher uses
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            self.add(u64::from_le_bytes(word.try_into().expect("chunks of eight")));
+        }
+        for &byte in words.remainder() {
+            self.add(byte as u64);
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.add(n as u64);
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.add(n as u64);
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.add(n as u64);
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.add(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.add(n as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn test_spreads_grid_positions() {
+        let hasher = BuildHasherDefault::<StateHasher>::default();
+        let hashes = (0..100)
+            .flat_map(|x| (0..100).map(move |y| (x, y)))
+            .map(|pos: (i32, i32)| hasher.hash_one(pos))
+            .collect::<StateSet<_>>();
+
+        assert_eq!(10_000, hashes.len());
+        // hashbrown picks buckets with the low bits and tags with the top seven
+        assert_eq!(128, hashes.iter().map(|hash| hash >> 57).collect::<StateSet<_>>().len());
+        assert_eq!(1024, hashes.iter().map(|hash| hash & 1023).collect::<StateSet<_>>().len());
+    }
+}
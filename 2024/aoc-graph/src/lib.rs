@@ -0,0 +1,128 @@
+//! Searches over graphs that are never built, only described by a `neighbors`
+//! closure handing out the states one step away (and what the step costs).
+//!
+//! States only need to be hashable and cloneable, so a position, a position with
+//! a facing or a whole puzzle state all work the same way.
+
+use std::{collections::hash_map::Entry, hash::Hash, ops::Add};
+
+mod bfs;
+mod dijkstra;
+mod hash;
+mod optimal;
+
+use hash::StateMap;
+
+pub use bfs::{bfs, bfs_len, bfs_reach, bfs_reach_dense, bidirectional_bfs, DenseReach, UNREACHED};
+pub use dijkstra::{astar, dijkstra};
+pub use optimal::{dijkstra_all, Optimal};
+
+/// Anything edge costs can be summed and compared in, `Default` being zero
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+const ROOT: usize = usize::MAX;
+
+/// Every state seen so far, numbered in the order they were found, with the
+/// number of the state they were reached from
+#[derive(Debug)]
+pub(crate) struct Visited<N> {
+    nodes: Vec<N>,
+    index: StateMap<N, usize>,
+    parents: Vec<usize>,
+}
+
+impl<N: Eq + Hash + Clone> Visited<N> {
+    pub(crate) fn new(start: N) -> Self {
+        let mut visited = Self {
+            nodes: Vec::new(),
+            index: StateMap::default(),
+            parents: Vec::new(),
+        };
+        visited.insert(start, ROOT);
+        visited
+    }
+
+    /// The new state's number, or `None` if it was already seen
+    pub(crate) fn insert(&mut self, node: N, parent: usize) -> Option<usize> {
+        match self.index.entry(node) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => {
+                let idx = self.nodes.len();
+                self.nodes.push(entry.key().clone());
+                self.parents.push(parent);
+                entry.insert(idx);
+                Some(idx)
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    pub(crate) fn node(&self, idx: usize) -> &N {
+        &self.nodes[idx]
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// From the start to `idx`
+    pub(crate) fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[idx].clone()];
+        while self.parents[idx] != ROOT {
+            idx = self.parents[idx];
+            path.push(self.nodes[idx].clone());
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    /// A `width` x `height` grid with walls wherever `blocked` says, in four directions
+    pub(crate) fn grid(
+        width: i32,
+        height: i32,
+        blocked: impl Fn(i32, i32) -> bool,
+    ) -> impl Fn(&(i32, i32)) -> Vec<(i32, i32)> {
+        move |&(x, y)| {
+            [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+                .into_iter()
+                .filter(|&(x, y)| x >= 0 && y >= 0 && x < width && y < height && !blocked(x, y))
+                .collect()
+        }
+    }
+
+    /// The day 18 example, with the first 12 bytes fallen on a 7x7 grid
+    pub(crate) const CORRUPTED: [(i32, i32); 12] = [
+        (5, 4),
+        (4, 2),
+        (4, 5),
+        (3, 0),
+        (2, 1),
+        (6, 3),
+        (2, 4),
+        (1, 5),
+        (0, 6),
+        (3, 3),
+        (2, 6),
+        (5, 1),
+    ];
+
+    #[test]
+    fn test_path_follows_parents() {
+        let mut visited = super::Visited::new('a');
+        let b = visited.insert('b', 0).unwrap();
+        let c = visited.insert('c', b).unwrap();
+
+        assert_eq!(None, visited.insert('b', c));
+        assert_eq!(vec!['a', 'b', 'c'], visited.path(c));
+        assert_eq!(vec!['a'], visited.path(0));
+    }
+}
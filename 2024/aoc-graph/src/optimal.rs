@@ -0,0 +1,276 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{hash_map::Entry, BinaryHeap},
+    hash::Hash,
+};
+
+use crate::{Cost, StateMap};
+
+/// Every cheapest way to every state a [`dijkstra_all`] settled. Each state keeps
+/// all of its predecessors along cheapest paths, which makes a DAG of the optimal
+/// paths rather than the single tree the other searches keep.
+#[derive(Debug, Clone)]
+pub struct Optimal<N, C> {
+    nodes: Vec<N>,
+    index: StateMap<N, usize>,
+    costs: Vec<C>,
+    predecessors: Vec<Vec<usize>>,
+    /// cheapest first, only the states that are final
+    order: Vec<usize>,
+    /// the goal states that tied for cheapest
+    goals: Vec<usize>,
+}
+
+/// Dijkstra keeping every tie, until the states costing as much as the cheapest
+/// goal are all done (pass `|_| false` to go everywhere).
+///
+/// Steps have to cost more than zero, otherwise states could end up as each
+/// other's predecessors.
+pub fn dijkstra_all<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Optimal<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let zero = C::default();
+    let mut optimal = Optimal {
+        nodes: vec![start.clone()],
+        index: StateMap::from_iter([(start, 0)]),
+        costs: vec![zero],
+        predecessors: vec![Vec::new()],
+        order: Vec::new(),
+        goals: Vec::new(),
+    };
+    let mut settled = vec![false];
+    let mut heap = BinaryHeap::from([Reverse((zero, 0))]);
+    let mut goal_cost = None;
+
+    while let Some(Reverse((cost, current))) = heap.pop() {
+        if settled[current] {
+            continue;
+        }
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        settled[current] = true;
+        optimal.order.push(current);
+
+        if goal(&optimal.nodes[current]) {
+            goal_cost = Some(cost);
+            optimal.goals.push(current);
+        }
+
+        for (next, step) in neighbors(&optimal.nodes[current]) {
+            let next_cost = cost + step;
+            match optimal.index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let idx = *entry.get();
+                    if settled[idx] {
+                        continue;
+                    }
+                    match next_cost.cmp(&optimal.costs[idx]) {
+                        Ordering::Less => {
+                            optimal.costs[idx] = next_cost;
+                            optimal.predecessors[idx].clear();
+                            optimal.predecessors[idx].push(current);
+                            heap.push(Reverse((next_cost, idx)));
+                        }
+                        Ordering::Equal => optimal.predecessors[idx].push(current),
+                        Ordering::Greater => {}
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    let idx = optimal.nodes.len();
+                    optimal.nodes.push(entry.key().clone());
+                    optimal.costs.push(next_cost);
+                    optimal.predecessors.push(vec![current]);
+                    settled.push(false);
+                    entry.insert(idx);
+                    heap.push(Reverse((next_cost, idx)));
+                }
+            }
+        }
+    }
+
+    // anything still queued might have a cheaper way in that was never looked at
+    optimal.index.retain(|_, idx| settled[*idx]);
+
+    optimal
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> Optimal<N, C> {
+    /// The cheapest way to `state`, if it was settled
+    pub fn cost(&self, state: &N) -> Option<C> {
+        self.index.get(state).map(|&idx| self.costs[idx])
+    }
+
+    /// The cheapest goal states, all costing the same
+    pub fn goals(&self) -> impl Iterator<Item = &N> {
+        self.goals.iter().map(|&idx| &self.nodes[idx])
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goals.first().map(|&idx| self.costs[idx])
+    }
+
+    /// Settled states with their cost, cheapest first
+    pub fn reached(&self) -> impl Iterator<Item = (&N, C)> {
+        self.order.iter().map(|&idx| (&self.nodes[idx], self.costs[idx]))
+    }
+
+    /// The states one step before `state` on any of its cheapest paths
+    pub fn predecessors(&self, state: &N) -> impl Iterator<Item = &N> {
+        self.index
+            .get(state)
+            .map_or(&[][..], |&idx| &self.predecessors[idx])
+            .iter()
+            .map(|&idx| &self.nodes[idx])
+    }
+
+    /// One cheapest path from the start to `target`
+    pub fn path(&self, target: &N) -> Option<Vec<N>> {
+        let mut idx = *self.index.get(target)?;
+        let mut path = vec![self.nodes[idx].clone()];
+        while let Some(&previous) = self.predecessors[idx].first() {
+            idx = previous;
+            path.push(self.nodes[idx].clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every cheapest path from the start to `target`. There can be exponentially
+    /// many, [`Optimal::count_paths`] and [`Optimal::on_paths`] don't list them.
+    pub fn paths(&self, target: &N) -> Vec<Vec<N>> {
+        let Some(&idx) = self.index.get(target) else {
+            return Vec::new();
+        };
+
+        let mut paths = Vec::new();
+        let mut stack = vec![(idx, vec![idx])];
+        while let Some((idx, reversed)) = stack.pop() {
+            if self.predecessors[idx].is_empty() {
+                paths.push(reversed.iter().rev().map(|&idx| self.nodes[idx].clone()).collect());
+                continue;
+            }
+            for &previous in &self.predecessors[idx] {
+                let mut longer = reversed.clone();
+                longer.push(previous);
+                stack.push((previous, longer));
+            }
+        }
+
+        paths
+    }
+
+    /// How many cheapest paths lead to `target`
+    pub fn count_paths(&self, target: &N) -> u64 {
+        let Some(&target) = self.index.get(target) else {
+            return 0;
+        };
+
+        // predecessors are always cheaper, so they're counted first
+        let mut counts = vec![0_u64; self.nodes.len()];
+        for &idx in &self.order {
+            counts[idx] = if self.predecessors[idx].is_empty() {
+                1
+            } else {
+                self.predecessors[idx].iter().map(|&previous| counts[previous]).sum()
+            };
+            if idx == target {
+                break;
+            }
+        }
+
+        counts[target]
+    }
+
+    /// Every state on at least one cheapest path to any of `targets`
+    pub fn on_paths<'a>(&'a self, targets: impl IntoIterator<Item = &'a N>) -> Vec<&'a N> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = targets
+            .into_iter()
+            .filter_map(|target| self.index.get(target).copied())
+            .collect::<Vec<_>>();
+
+        let mut states = Vec::new();
+        while let Some(idx) = stack.pop() {
+            if std::mem::replace(&mut seen[idx], true) {
+                continue;
+            }
+            states.push(&self.nodes[idx]);
+            stack.extend(&self.predecessors[idx]);
+        }
+
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::{dijkstra, tests::grid};
+
+    #[rstest]
+    #[case((1, 1), 2)]
+    #[case((2, 2), 6)]
+    #[case((3, 1), 4)]
+    #[case((4, 4), 70)]
+    fn test_lattice_paths(#[case] target: (i32, i32), #[case] expected: u64) {
+        let neighbors = grid(5, 5, |_, _| false);
+        let optimal = dijkstra_all((0, 0), |pos| neighbors(pos).into_iter().map(|next| (next, 1)), |_| false);
+
+        assert_eq!(expected, optimal.count_paths(&target));
+        assert_eq!(expected as usize, optimal.paths(&target).len());
+        assert_eq!(Some(target.0 + target.1), optimal.cost(&target));
+
+        let on_paths = optimal.on_paths([&target]);
+        assert_eq!(((target.0 + 1) * (target.1 + 1)) as usize, on_paths.len());
+    }
+
+    #[test]
+    fn test_stops_after_the_cheapest_goals() {
+        // two ways round to 3 cost 2, straight through the middle costs 3
+        let neighbors = |&n: &u8| match n {
+            0 => vec![(1, 1), (2, 1), (3, 3)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        };
+        let optimal = dijkstra_all(0, neighbors, |&n| n == 3);
+
+        assert_eq!(Some(2), optimal.goal_cost());
+        assert_eq!(vec![&3], optimal.goals().collect::<Vec<_>>());
+        assert_eq!(2, optimal.count_paths(&3));
+        assert_eq!(vec![&1, &2], {
+            let mut previous = optimal.predecessors(&3).collect::<Vec<_>>();
+            previous.sort();
+            previous
+        });
+        assert_eq!(None, optimal.cost(&4));
+
+        let paths = optimal.paths(&3);
+        let (path, cost) = dijkstra(0, neighbors, |&n| n == 3).unwrap();
+        assert_eq!(2, cost);
+        assert!(paths.contains(&path));
+        assert!(paths.contains(&optimal.path(&3).unwrap()));
+        assert_eq!(4, optimal.on_paths(optimal.goals()).len());
+    }
+
+    #[test]
+    fn test_reached_is_cheapest_first() {
+        let optimal = dijkstra_all(1_u32, |&n| [(n * 3, 2), (n + 1, 1)].into_iter().filter(|&(n, _)| n <= 20), |_| false);
+
+        assert_eq!(20, optimal.reached().count());
+        assert!(optimal.reached().collect::<Vec<_>>().windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(None, optimal.goal_cost());
+    }
+}
@@ -0,0 +1,111 @@
+//! The maths a lot of puzzles turn into once they're looked at hard enough:
+//! divisibility and modular arithmetic, congruences, and linear systems solved
+//! exactly rather than in floats.
+//!
+//! Everything beyond [`gcd`] and [`lcm`] works in `i128`, which leaves room for
+//! part 2 sized numbers being multiplied together.
+
+use std::ops::{Div, Mul, Rem, Sub};
+
+mod linear;
+mod number;
+
+pub use linear::{solve_2x2, solve_linear, Rational};
+pub use number::{
+    crt, diophantine, div_ceil, div_floor, exact_sqrt, extended_gcd, isqrt, mod_inverse, mod_pow, Diophantine,
+};
+
+/// The primitive integers [`gcd`] and [`lcm`] work on
+pub trait Integer: Copy + Ord + Rem<Output = Self> + Div<Output = Self> + Mul<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! integer {
+    (signed $($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+        }
+    )*};
+    (unsigned $($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn abs(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+integer!(signed i8 i16 i32 i64 i128 isize);
+integer!(unsigned u8 u16 u32 u64 u128 usize);
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Least common multiple, never negative, and 0 if either is
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::testing::Numbers;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(12, 18, 6)]
+    #[case(-12, 18, 6)]
+    #[case(12, -18, 6)]
+    #[case(0, 7, 7)]
+    #[case(7, 0, 7)]
+    #[case(0, 0, 0)]
+    #[case(17, 5, 1)]
+    fn test_gcd(#[case] a: i32, #[case] b: i32, #[case] expected: i32) {
+        assert_eq!(expected, gcd(a, b));
+        assert_eq!(expected as u64, gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64));
+    }
+
+    #[rstest]
+    #[case(4, 6, 12)]
+    #[case(-4, 6, 12)]
+    #[case(101, 103, 10403)]
+    #[case(0, 5, 0)]
+    fn test_lcm(#[case] a: i64, #[case] b: i64, #[case] expected: i64) {
+        assert_eq!(expected, lcm(a, b));
+    }
+
+    #[test]
+    fn test_gcd_divides_and_lcm_is_multiple() {
+        let mut numbers = Numbers::new(48);
+        for _ in 0..1000 {
+            let (a, b) = (numbers.between(-10_000, 10_000), numbers.between(-10_000, 10_000));
+            let (g, l) = (gcd(a, b), lcm(a, b));
+
+            if a == 0 && b == 0 {
+                assert_eq!((0, 0), (g, l));
+                continue;
+            }
+            assert_eq!(0, a % g);
+            assert_eq!(0, b % g);
+            assert_eq!(1, gcd(a / g, b / g));
+            assert_eq!((a * b).abs(), g * l);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{gcd, lcm};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator so
+/// equal values compare and hash equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "{numer}/0 isn't a number");
+
+        let g = gcd(numer, denom) * denom.signum();
+        Self { numer: numer / g, denom: denom / g }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denom == 1
+    }
+
+    /// The value if it's a whole number
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numer)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Self { numer: n, denom: 1 }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let denom = lcm(self.denom, other.denom);
+        Self::new(self.numer * (denom / self.denom) + other.numer * (denom / other.denom), denom)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { numer: -self.numer, denom: self.denom }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // cancelling across first keeps the products as small as they can be
+        let a = gcd(self.numer, other.denom).max(1);
+        let b = gcd(other.numer, self.denom).max(1);
+        Self::new((self.numer / a) * (other.numer / b), (self.denom / b) * (other.denom / a))
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let a = gcd(self.numer, other.numer).max(1);
+        let b = gcd(self.denom, other.denom);
+        Self::new((self.numer / a) * (other.denom / b), (self.denom / b) * (other.numer / a))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The one solution to `matrix * [x, y] = rhs` by Cramer's rule, `None` if the
+/// determinant is zero and there's either none or infinitely many
+pub fn solve_2x2(matrix: [[i128; 2]; 2], rhs: [i128; 2]) -> Option<[Rational; 2]> {
+    let [[a, b], [c, d]] = matrix;
+    let determinant = a * d - b * c;
+    if determinant == 0 {
+        return None;
+    }
+
+    Some([
+        Rational::new(d * rhs[0] - b * rhs[1], determinant),
+        Rational::new(a * rhs[1] - c * rhs[0], determinant),
+    ])
+}
+
+/// The one solution to `matrix * x = rhs` for a square matrix, `None` if it's
+/// singular.
+///
+/// Elimination is fraction free (Bareiss), so everything stays an integer no bigger
+/// than a determinant of the input until the final back substitution.
+pub fn solve_linear<R: AsRef<[i128]>>(matrix: &[R], rhs: &[i128]) -> Option<Vec<Rational>> {
+    let n = rhs.len();
+    assert_eq!(n, matrix.len(), "one right hand side per row");
+
+    let mut rows = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &rhs)| {
+            let row = row.as_ref();
+            assert_eq!(n, row.len(), "the matrix has to be square");
+            row.iter().copied().chain([rhs]).collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut previous = 1;
+    for k in 0..n {
+        let pivot = (k..n).find(|&row| rows[row][k] != 0)?;
+        rows.swap(k, pivot);
+
+        for i in k + 1..n {
+            for j in k + 1..=n {
+                // always divides exactly, that's the trick
+                rows[i][j] = (rows[i][j] * rows[k][k] - rows[i][k] * rows[k][j]) / previous;
+            }
+            rows[i][k] = 0;
+        }
+        previous = rows[k][k];
+    }
+
+    let mut solution = vec![Rational::ZERO; n];
+    for i in (0..n).rev() {
+        let known = (i + 1..n).fold(Rational::ZERO, |sum, j| sum + Rational::from(rows[i][j]) * solution[j]);
+        solution[i] = (Rational::from(rows[i][n]) - known) / Rational::from(rows[i][i]);
+    }
+
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::testing::Numbers;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Rational::new(2, 4), Rational::new(-3, -6))]
+    #[case(Rational::new(3, -9), Rational::new(-1, 3))]
+    #[case(Rational::new(0, -5), Rational::ZERO)]
+    #[case(Rational::new(1, 3) + Rational::new(1, 6), Rational::new(1, 2))]
+    #[case(Rational::new(1, 3) - Rational::new(1, 2), Rational::new(-1, 6))]
+    #[case(Rational::new(2, 3) * Rational::new(9, 4), Rational::new(3, 2))]
+    #[case(Rational::new(2, 3) / Rational::new(-4, 9), Rational::new(-3, 2))]
+    fn test_rational_arithmetic(#[case] value: Rational, #[case] expected: Rational) {
+        assert_eq!(expected, value);
+        assert!(value.denom() > 0);
+    }
+
+    #[test]
+    fn test_rational_order() {
+        let mut fractions = [Rational::new(1, 2), Rational::new(-3, 4), Rational::from(2), Rational::new(1, 3)];
+        fractions.sort();
+
+        assert_eq!("-3/4 1/3 1/2 2", fractions.map(|f| f.to_string()).join(" "));
+        assert_eq!(Some(2), fractions[3].to_integer());
+        assert_eq!(None, fractions[2].to_integer());
+    }
+
+    #[rstest]
+    // day 13's first claw machine
+    #[case([[94, 22], [34, 67]], [8400, 5400], Some([Rational::from(80), Rational::from(40)]))]
+    #[case([[26, 67], [66, 21]], [12748, 12176], Some([Rational::new(137_021, 969), Rational::new(131_198, 969)]))]
+    #[case([[2, 4], [1, 2]], [6, 3], None)]
+    fn test_solve_2x2(#[case] matrix: [[i128; 2]; 2], #[case] rhs: [i128; 2], #[case] expected: Option<[Rational; 2]>) {
+        assert_eq!(expected, solve_2x2(matrix, rhs));
+        assert_eq!(expected.map(Vec::from), solve_linear(&matrix, &rhs));
+    }
+
+    #[test]
+    fn test_needs_a_row_swap() {
+        let matrix = [[0, 1, 1], [1, 0, 1], [1, 1, 0]];
+
+        assert_eq!(
+            Some(vec![Rational::new(1, 2), Rational::new(3, 2), Rational::new(5, 2)]),
+            solve_linear(&matrix, &[4, 3, 2])
+        );
+        assert_eq!(None, solve_linear(&[[1, 2, 3], [2, 4, 6], [0, 0, 1]], &[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_solves_back_to_the_answer() {
+        let mut numbers = Numbers::new(50);
+        for _ in 0..300 {
+            let n = numbers.between(1, 6) as usize;
+            let matrix = (0..n)
+                .map(|_| (0..n).map(|_| numbers.between(-20, 20)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let answer = (0..n).map(|_| numbers.between(-1000, 1000)).collect::<Vec<_>>();
+            let rhs = matrix
+                .iter()
+                .map(|row| row.iter().zip(&answer).map(|(a, x)| a * x).sum())
+                .collect::<Vec<_>>();
+
+            // random matrices are singular every now and then, which is a solution short
+            if let Some(solution) = solve_linear(&matrix, &rhs) {
+                assert_eq!(answer, solution.iter().map(|x| x.to_integer().unwrap()).collect::<Vec<_>>());
+            }
+            if n == 2 {
+                let matrix = [[matrix[0][0], matrix[0][1]], [matrix[1][0], matrix[1][1]]];
+                assert_eq!(solve_linear(&matrix, &rhs), solve_2x2(matrix, [rhs[0], rhs[1]]).map(Vec::from));
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+/// Returns (g, x, y) such that a*x + b*y = g = gcd(a, b), with g >= 0
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Division rounding towards negative infinity, where `/` rounds towards zero
+pub fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// Division rounding towards positive infinity
+pub fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+/// Every integer solution to `a * x + b * y = c`, one for each `k` in [`Diophantine::at`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diophantine {
+    pub x: i128,
+    pub y: i128,
+    /// what one more `k` adds to x, never zero
+    pub dx: i128,
+    /// what one more `k` adds to y, the opposite sign to `dx` when `a` and `b` share one
+    pub dy: i128,
+}
+
+impl Diophantine {
+    pub fn at(&self, k: i128) -> (i128, i128) {
+        (self.x + k * self.dx, self.y + k * self.dy)
+    }
+}
+
+/// Integer solutions to `a * x + b * y = c`, or `None` if there aren't any.
+///
+/// With `a` and `b` both zero every pair or no pair solves it, neither of which
+/// fits in a [`Diophantine`], so that's `None` too.
+pub fn diophantine(a: i128, b: i128, c: i128) -> Option<Diophantine> {
+    if a == 0 && b == 0 {
+        return None;
+    }
+
+    let (g, x, y) = extended_gcd(a, b);
+    if c % g != 0 {
+        return None;
+    }
+
+    Some(Diophantine {
+        x: x * (c / g),
+        y: y * (c / g),
+        dx: b / g,
+        dy: -a / g,
+    })
+}
+
+/// `a * b % modulus`, going bit by bit when the product doesn't fit
+fn mul_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    let (a, b) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    // both are below 2^127, so doubling stays inside a u128
+    let (mut a, mut b, modulus) = (a as u128, b as u128, modulus as u128);
+    let mut product = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = (product + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+
+    product as i128
+}
+
+/// `base ^ exp % modulus`, in `0..modulus`
+pub fn mod_pow(base: i128, mut exp: u128, modulus: i128) -> i128 {
+    assert!(modulus > 0, "modulus has to be positive, got {modulus}");
+
+    let mut base = base.rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// The x in `0..modulus` with `a * x = 1 (mod modulus)`, if `a` and `modulus` are coprime
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    assert!(modulus > 0, "modulus has to be positive, got {modulus}");
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Chinese remainder theorem for `x = residue (mod modulus)` pairs, where the moduli
+/// don't have to be coprime. Gives the smallest non-negative x and the lcm of the
+/// moduli it repeats with, or `None` if the congruences contradict each other.
+///
+/// No congruences at all is `Some((0, 1))`, since every number fits.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences.into_iter().try_fold((0, 1), |(r1, m1), (r2, m2)| {
+        assert!(m2 > 0, "moduli have to be positive, got {m2}");
+
+        let r2 = r2.rem_euclid(m2);
+        let (g, p, _) = extended_gcd(m1, m2);
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+
+        // m1 * p = g (mod m2), so r1 + m1 * t lands on r2 for t = (r2 - r1) / g * p
+        let t = mul_mod((r2 - r1) / g, p, m2 / g);
+        let modulus = (m1 / g).checked_mul(m2).expect("combined modulus overflows i128");
+
+        Some(((r1 + m1 * t).rem_euclid(modulus), modulus))
+    })
+}
+
+/// Square root rounded down, `None` for negative numbers
+pub fn isqrt(n: i128) -> Option<i128> {
+    n.checked_isqrt()
+}
+
+/// The square root of `n` if it's a perfect square
+pub fn exact_sqrt(n: i128) -> Option<i128> {
+    isqrt(n).filter(|root| root * root == n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::testing::Numbers;
+    use rstest::rstest;
+
+    use crate::{gcd, lcm};
+
+    #[rstest]
+    #[case(240, 46, (2, -9, 47))]
+    #[case(-4, 6, (2, 1, 1))]
+    #[case(0, 5, (5, 0, 1))]
+    fn test_extended_gcd(#[case] a: i128, #[case] b: i128, #[case] expected: (i128, i128, i128)) {
+        let (g, x, y) = extended_gcd(a, b);
+
+        assert_eq!(expected, (g, x, y));
+        assert_eq!(g, a * x + b * y);
+    }
+
+    #[test]
+    fn test_bezout() {
+        let mut numbers = Numbers::new(13);
+        for _ in 0..1000 {
+            let (a, b) = (numbers.between(-1_000_000, 1_000_000), numbers.between(-1_000_000, 1_000_000));
+            let (g, x, y) = extended_gcd(a, b);
+
+            assert_eq!(gcd(a, b), g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[rstest]
+    #[case(7, 2, 3, 4)]
+    #[case(-7, 2, -4, -3)]
+    #[case(7, -2, -4, -3)]
+    #[case(-7, -2, 3, 4)]
+    #[case(6, 3, 2, 2)]
+    fn test_rounding(#[case] a: i128, #[case] b: i128, #[case] floor: i128, #[case] ceil: i128) {
+        assert_eq!(floor, div_floor(a, b));
+        assert_eq!(ceil, div_ceil(a, b));
+    }
+
+    #[test]
+    fn test_diophantine() {
+        let mut numbers = Numbers::new(8);
+        for _ in 0..1000 {
+            let (a, b) = (numbers.between(-500, 500), numbers.between(-500, 500));
+            let c = numbers.between(-100_000, 100_000);
+
+            let Some(solutions) = diophantine(a, b, c) else {
+                assert!((a, b) == (0, 0) || c % gcd(a, b) != 0);
+                continue;
+            };
+            assert_ne!(0, solutions.dx);
+            for k in -3..=3 {
+                let (x, y) = solutions.at(k);
+                assert_eq!(c, a * x + b * y);
+            }
+        }
+
+        // 3x + 5y = 97 has its smallest non-negative x at 4
+        let solutions = diophantine(3, 5, 97).unwrap();
+        let k = div_ceil(-solutions.x, solutions.dx);
+        assert_eq!((4, 17), solutions.at(k));
+        assert_eq!(None, diophantine(4, 6, 5));
+        assert_eq!(None, diophantine(0, 0, 0));
+    }
+
+    #[rstest]
+    #[case(2, 10, 1000, 24)]
+    #[case(3, 0, 7, 1)]
+    #[case(5, 3, 1, 0)]
+    #[case(-2, 3, 7, 6)]
+    // 2^127 - 1 is prime, so Fermat says this is 1, and the products overflow i128
+    #[case(3, (1 << 127) - 2, i128::MAX, 1)]
+    fn test_mod_pow(#[case] base: i128, #[case] exp: u128, #[case] modulus: i128, #[case] expected: i128) {
+        assert_eq!(expected, mod_pow(base, exp, modulus));
+    }
+
+    #[test]
+    fn test_mod_pow_multiplies() {
+        let mut numbers = Numbers::new(22);
+        for _ in 0..200 {
+            let base = numbers.between(-1000, 1000);
+            let exp = numbers.between(0, 40) as u128;
+            let modulus = numbers.between(1, 16_777_216);
+
+            let naive = (0..exp).fold(1 % modulus, |product, _| (product * base).rem_euclid(modulus));
+            assert_eq!(naive, mod_pow(base, exp, modulus));
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(Some(7), mod_inverse(-3, 11));
+        assert_eq!(None, mod_inverse(4, 6));
+
+        let mut numbers = Numbers::new(11);
+        for _ in 0..1000 {
+            let (a, modulus) = (numbers.between(-1_000_000, 1_000_000), numbers.between(1, 1_000_000));
+            match mod_inverse(a, modulus) {
+                Some(inverse) => {
+                    assert!((0..modulus).contains(&inverse));
+                    assert_eq!(1 % modulus, (a * inverse).rem_euclid(modulus));
+                }
+                None => assert_ne!(1, gcd(a, modulus)),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(vec![(2, 3), (3, 5), (2, 7)], Some((23, 105)))]
+    // not coprime but agreeing on their common factor
+    #[case(vec![(2, 4), (4, 6)], Some((10, 12)))]
+    #[case(vec![(1, 4), (2, 6)], None)]
+    #[case(vec![(-1, 5)], Some((4, 5)))]
+    #[case(vec![], Some((0, 1)))]
+    fn test_crt(#[case] congruences: Vec<(i128, i128)>, #[case] expected: Option<(i128, i128)>) {
+        assert_eq!(expected, crt(congruences));
+    }
+
+    #[test]
+    fn test_crt_finds_the_number() {
+        let mut numbers = Numbers::new(14);
+        for _ in 0..1000 {
+            let x = numbers.between(0, 1_000_000_000);
+            let moduli = (0..numbers.between(1, 4)).map(|_| numbers.between(1, 200)).collect::<Vec<_>>();
+            let modulus = moduli.iter().fold(1, |modulus, &m| lcm(modulus, m));
+
+            let found = crt(moduli.iter().map(|&m| (x % m, m)));
+            assert_eq!(Some((x % modulus, modulus)), found);
+        }
+    }
+
+    #[rstest]
+    #[case(0, Some(0))]
+    #[case(15, Some(3))]
+    #[case(16, Some(4))]
+    #[case(-1, None)]
+    #[case(i128::MAX, Some(13_043_817_825_332_782_212))]
+    fn test_isqrt(#[case] n: i128, #[case] expected: Option<i128>) {
+        assert_eq!(expected, isqrt(n));
+    }
+
+    #[test]
+    fn test_exact_sqrt() {
+        let mut numbers = Numbers::new(2);
+        for _ in 0..1000 {
+            let root = numbers.between(0, 1 << 60);
+
+            assert_eq!(Some(root), exact_sqrt(root * root));
+            if root > 0 {
+                assert_eq!(None, exact_sqrt(root * root + 1));
+                assert_eq!(Some(root), isqrt(root * root + 2 * root));
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use glam::IVec2;
+use nom::{
+    character::complete::line_ending,
+    error::{Error, ErrorKind},
+    IResult,
+};
+
+/// A rectangle of cells, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    pub width: i32,
+    pub height: i32,
+    pub cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn contains(&self, position: IVec2) -> bool {
+        position.x >= 0 && position.y >= 0 && position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[(position.y * self.width + position.x) as usize])
+    }
+
+    /// Every cell with where it is, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (IVec2::new(i as i32 % width, i as i32 / width), cell))
+    }
+
+    /// Where the first cell `predicate` likes is
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.positions().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+}
+
+/// Rows of characters `cell` turns into something, all the same length. Stops
+/// before a blank line or the end of the input.
+///
+/// A character `cell` says `None` to is an error right there, so are rows that
+/// are longer or shorter than the first.
+pub fn grid<'a, T>(mut cell: impl FnMut(char) -> Option<T>) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |input: &'a str| {
+        let mut cells = Vec::new();
+        let (mut width, mut height) = (0, 0);
+        let mut rest = input;
+
+        loop {
+            let row = &rest[..rest.find(['\r', '\n']).unwrap_or(rest.len())];
+            if row.is_empty() {
+                break;
+            }
+
+            for (i, c) in row.char_indices() {
+                let Some(value) = cell(c) else {
+                    let error = Error::new(&rest[i..], ErrorKind::Char);
+                    // only the very first character leaves room for something else to be tried
+                    return Err(if cells.is_empty() { nom::Err::Error(error) } else { nom::Err::Failure(error) });
+                };
+                cells.push(value);
+            }
+
+            let row_width = row.chars().count() as i32;
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(nom::Err::Failure(Error::new(rest, ErrorKind::Verify)));
+            }
+            height += 1;
+
+            // only carry on if there's another row right below
+            let after = &rest[row.len()..];
+            match line_ending::<_, Error<&str>>(after) {
+                Ok((next, _)) if !next.is_empty() && !next.starts_with(['\r', '\n']) => rest = next,
+                _ => {
+                    rest = after;
+                    break;
+                }
+            }
+        }
+
+        if width == 0 {
+            return Err(nom::Err::Error(Error::new(input, ErrorKind::Many1)));
+        }
+
+        Ok((rest, Grid { width, height, cells }))
+    }
+}
+
+/// A [`grid`] of whatever characters are there
+pub fn char_grid(input: &str) -> IResult<&str, Grid<char>> {
+    grid(Some)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::blank_line;
+
+    const WAREHOUSE: &str = "#####\n#.O@#\n#####\n\n<^^>";
+
+    #[test]
+    fn test_warehouse() {
+        let (rest, warehouse) = char_grid(WAREHOUSE).unwrap();
+
+        assert_eq!("\n\n<^^>", rest);
+        assert_eq!((5, 3), (warehouse.width, warehouse.height));
+        assert_eq!(Some(IVec2::new(3, 1)), warehouse.find(|&c| c == '@'));
+        assert_eq!(Some(&'O'), warehouse.get(IVec2::new(2, 1)));
+        assert_eq!(None, warehouse.get(IVec2::new(5, 1)));
+        assert_eq!(Ok(("<^^>", "\n\n")), blank_line(rest));
+    }
+
+    #[test]
+    fn test_digits() {
+        let (_, heights) = grid(|c| c.to_digit(10))("0123\r\n1234\r\n").unwrap();
+
+        assert_eq!(vec![0, 1, 2, 3, 1, 2, 3, 4], heights.cells);
+        assert_eq!(
+            vec![(IVec2::new(3, 0), &3), (IVec2::new(0, 1), &1)],
+            heights.positions().skip(3).take(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    // a character that isn't a cell
+    #[case("01\n2x", Err(nom::Err::Failure(("x", ErrorKind::Char))))]
+    #[case("x1\n23", Err(nom::Err::Error(("x1\n23", ErrorKind::Char))))]
+    // rows of different lengths
+    #[case("01\n234\n56", Err(nom::Err::Failure(("234\n56", ErrorKind::Verify))))]
+    #[case("", Err(nom::Err::Error(("", ErrorKind::Many1))))]
+    #[case("\n01", Err(nom::Err::Error(("\n01", ErrorKind::Many1))))]
+    fn test_not_a_grid(#[case] input: &str, #[case] expected: Result<(), nom::Err<(&str, ErrorKind)>>) {
+        let result = grid(|c| c.to_digit(10))(input)
+            .map(|_| ())
+            .map_err(|error| error.map(|error| (error.input, error.code)));
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,87 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::space0,
+    sequence::{pair, preceded, separated_pair, tuple},
+    IResult,
+};
+
+/// `value` after `label` and any spaces, like `labelled("Register A:", signed)` for
+/// `Register A: 729` or `labelled("X", signed)` for `X+94`
+pub fn labelled<'a, O>(
+    label: &'static str,
+    value: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(pair(tag(label), space0), value)
+}
+
+/// `key`, then `separator` with any spaces around it, then `value`, like `x00: 1`
+/// or `ntg XOR fgs -> mjb`
+pub fn key_value<'a, K, V>(
+    key: impl FnMut(&'a str) -> IResult<&'a str, K>,
+    separator: &'static str,
+    value: impl FnMut(&'a str) -> IResult<&'a str, V>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)> {
+    separated_pair(key, tuple((space0, tag(separator), space0)), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use glam::IVec2;
+    use nom::{
+        character::complete::{alpha1, alphanumeric1, space1},
+        combinator::map,
+    };
+    use rstest::rstest;
+
+    use crate::{blank_line, comma_separated, ivec2, lines, signed, unsigned};
+
+    #[rstest]
+    #[case("Button A: X+94, Y+34", IVec2::new(94, 34))]
+    #[case("Button A: X-2, Y+0", IVec2::new(-2, 0))]
+    fn test_claw_machine(#[case] input: &str, #[case] expected: IVec2) {
+        let button = labelled(
+            "Button A:",
+            separated_pair(labelled("X", signed), tag(", "), labelled("Y", signed)),
+        );
+
+        assert_eq!(Ok(("", expected)), map(button, |(x, y)| IVec2::new(x, y))(input));
+        assert_eq!(Ok(("", 8400)), labelled("X=", signed::<i32>)("X=8400"));
+    }
+
+    #[test]
+    fn test_robot() {
+        let mut robot = separated_pair(labelled("p=", ivec2), space1, labelled("v=", ivec2));
+
+        assert_eq!(Ok(("", (IVec2::new(0, 4), IVec2::new(3, -3)))), robot("p=0,4 v=3,-3"));
+    }
+
+    #[test]
+    fn test_registers() {
+        let input = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0";
+        let registers = lines(preceded(tag("Register "), key_value(alpha1, ":", signed::<i64>)));
+        let program = labelled("Program:", comma_separated(unsigned::<u8>));
+
+        let (rest, (registers, program)) = separated_pair(registers, blank_line, program)(input).unwrap();
+        assert_eq!("", rest);
+        assert_eq!(vec![("A", 729), ("B", 0), ("C", 0)], registers);
+        assert_eq!(vec![0, 1, 5, 4, 3, 0], program);
+    }
+
+    #[rstest]
+    #[case("x00: 1", ("x00", 1))]
+    #[case("y01 :0", ("y01", 0))]
+    fn test_wire_values(#[case] input: &str, #[case] expected: (&str, u8)) {
+        assert_eq!(Ok(("", expected)), key_value(alphanumeric1, ":", unsigned)(input));
+    }
+
+    #[test]
+    fn test_gate() {
+        let gate = tuple((alphanumeric1, space1, alpha1, space1, alphanumeric1));
+        let (rest, ((left, _, op, _, right), output)) = key_value(gate, "->", alphanumeric1)("ntg XOR fgs -> mjb\n").unwrap();
+
+        assert_eq!(("ntg", "XOR", "fgs", "mjb"), (left, op, right, output));
+        assert_eq!("\n", rest);
+    }
+}
@@ -0,0 +1,77 @@
+//! nom parsers for the shapes puzzle inputs keep coming in: numbers, lists of
+//! them, lines, blank line separated sections, `label: value` pairs and grids.
+//!
+//! They're all plain nom 7 parsers over `&str` with nom's default error, so they
+//! mix with hand written ones, and [`parse_input`] runs the whole thing and turns
+//! a failure into an [`AocError`] pointing at where it went wrong.
+
+use aoc_core::AocError;
+use nom::{
+    character::complete::multispace0,
+    combinator::all_consuming,
+    sequence::terminated,
+    IResult,
+};
+
+mod grid;
+mod labelled;
+mod list;
+mod number;
+
+pub use grid::{char_grid, grid, Grid};
+pub use labelled::{key_value, labelled};
+pub use list::{blank_line, comma_separated, lines, pair_of, sections, spaced};
+pub use number::{ivec2, signed, unsigned, xy};
+
+/// Runs `parser` over the whole input, trailing whitespace aside, so anything it
+/// leaves behind is an error too
+pub fn parse_input<'a, O>(input: &'a str, parser: impl FnMut(&'a str) -> IResult<&'a str, O>) -> Result<O, AocError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .map_err(|error| AocError::from_nom(input, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{character::complete::line_ending, sequence::separated_pair};
+    use rstest::rstest;
+
+    fn span(error: AocError) -> (usize, usize) {
+        let AocError::ParseError { span, .. } = error else {
+            panic!("expected a parse error, got {error:?}");
+        };
+        (span.offset(), span.len())
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        assert_eq!(Ok(vec![1, 2, 3]), parse_input("1 2 3\n\n", spaced(unsigned::<u8>)).map_err(span));
+    }
+
+    #[rstest]
+    // the 'x' where a number should be
+    #[case("1 2\n3 x", (6, 1))]
+    // a number too big for a u8, which fails where it starts
+    #[case("1 2\n3 256", (6, 1))]
+    // left over at the end
+    #[case("1 2\n3 4;", (7, 1))]
+    fn test_errors_point_at_the_problem(#[case] input: &str, #[case] expected: (usize, usize)) {
+        let error = parse_input(input, lines(spaced(unsigned::<u8>))).unwrap_err();
+
+        assert_eq!(expected, span(error));
+    }
+
+    #[test]
+    fn test_errors_inside_sections() {
+        let input = "#.#\n.#.\n\n12\n3x";
+        let error = parse_input(input, separated_pair(char_grid, blank_line, lines(unsigned::<u32>))).unwrap_err();
+        assert_eq!((13, 1), span(error));
+
+        // the second row is short
+        let input = "#.#\n.#\n\n12";
+        let error = parse_input(input, separated_pair(char_grid, line_ending, lines(unsigned::<u32>))).unwrap_err();
+        assert_eq!(4, span(error).0);
+    }
+}
@@ -0,0 +1,129 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, line_ending, space0, space1},
+    combinator::recognize,
+    multi::separated_list1,
+    sequence::{pair, tuple},
+    IResult,
+};
+
+/// `item` over and over on one line, with spaces or tabs between (`7 6 4 2 1`)
+pub fn spaced<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// `item` separated by commas, spaces after them allowed (`75,47,61`)
+pub fn comma_separated<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// One `item` per line. Stops before a blank line, so it fits inside [`sections`].
+pub fn lines<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, item)
+}
+
+/// The end of one line and an empty one after it
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((line_ending, space0, line_ending)))(input)
+}
+
+/// Blocks separated by blank lines, each one a `section`, up to the end of the input.
+///
+/// Anything but whitespace after a blank line has to be another section, so a
+/// broken one is reported where it breaks rather than where it starts.
+pub fn sections<'a, O>(
+    mut section: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    move |input| {
+        let (mut rest, first) = section(input)?;
+        let mut sections = vec![first];
+
+        while let Ok((next, _)) = blank_line(rest) {
+            if next.trim().is_empty() {
+                break;
+            }
+            let (next, item) = section(next).map_err(|error| match error {
+                nom::Err::Error(error) => nom::Err::Failure(error),
+                error => error,
+            })?;
+            sections.push(item);
+            rest = next;
+        }
+
+        Ok((rest, sections))
+    }
+}
+
+/// Two `item`s either side of `separator` (`47|53`)
+pub fn pair_of<'a, O>(
+    separator: &'static str,
+    mut item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (O, O)> {
+    move |input| {
+        let (input, first) = item(input)?;
+        let (input, _) = tag(separator)(input)?;
+        let (input, second) = item(input)?;
+        Ok((input, (first, second)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{character::complete::alpha1, sequence::separated_pair};
+    use rstest::rstest;
+
+    use crate::{signed, unsigned};
+
+    #[rstest]
+    #[case("7 6 4 2 1", vec![7, 6, 4, 2, 1])]
+    #[case("1\t3   6", vec![1, 3, 6])]
+    #[case("125 17\n5", vec![125, 17])]
+    fn test_spaced(#[case] input: &str, #[case] expected: Vec<i32>) {
+        assert_eq!(expected, spaced(signed::<i32>)(input).unwrap().1);
+    }
+
+    #[test]
+    fn test_print_queue() {
+        // day 5's rules and updates
+        let input = "47|53\n97|13\n\n75,47,61\n97, 61,53\n";
+        let (rest, (rules, updates)) =
+            separated_pair(lines(pair_of("|", unsigned::<u32>)), blank_line, lines(comma_separated(unsigned::<u32>)))(
+                input,
+            )
+            .unwrap();
+
+        assert_eq!(vec![(47, 53), (97, 13)], rules);
+        assert_eq!(vec![vec![75, 47, 61], vec![97, 61, 53]], updates);
+        assert_eq!("\n", rest);
+    }
+
+    #[rstest]
+    #[case("ab\ncd\n\nef\n\ngh\nij", vec![vec!["ab", "cd"], vec!["ef"], vec!["gh", "ij"]])]
+    #[case("ab\r\n\r\ncd", vec![vec!["ab"], vec!["cd"]])]
+    #[case("ab\n  \ncd", vec![vec!["ab"], vec!["cd"]])]
+    fn test_sections(#[case] input: &str, #[case] expected: Vec<Vec<&str>>) {
+        assert_eq!(Ok(("", expected)), sections(lines(alpha1))(input));
+    }
+
+    #[test]
+    fn test_broken_section() {
+        let result = sections(lines(unsigned::<u8>))("1\n2\n\nx\n\n4");
+        assert_eq!(Err(nom::Err::Failure(nom::error::Error::new("x\n\n4", nom::error::ErrorKind::Digit))), result);
+
+        // trailing blank lines aren't a section
+        assert_eq!(Ok(("\n\n\n", vec![vec![1], vec![2]])), sections(lines(unsigned::<u8>))("1\n\n2\n\n\n"));
+    }
+
+    #[test]
+    fn test_lines_stop_at_the_first_that_isnt_one() {
+        assert_eq!(Ok(("\nx", vec![1, 2])), lines(unsigned::<u8>)("1\n2\nx"));
+    }
+}
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use glam::IVec2;
+use nom::{
+    character::complete::{char, digit1, one_of},
+    combinator::{map, map_res, opt, recognize},
+    sequence::{pair, separated_pair},
+    IResult,
+};
+
+/// A number with an optional sign, `+` included since some puzzles write it (`X+94`)
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// A number that's just digits
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// `x,y`
+pub fn xy<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(signed, char(','), signed)(input)
+}
+
+/// [`xy`] as a vector
+pub fn ivec2(input: &str) -> IResult<&str, IVec2> {
+    map(xy, |(x, y)| IVec2::new(x, y))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("42", Ok(("", 42)))]
+    #[case("-42,", Ok((",", -42)))]
+    #[case("+42", Ok(("", 42)))]
+    #[case("- 42", Err(()))]
+    #[case("x", Err(()))]
+    fn test_signed(#[case] input: &str, #[case] expected: Result<(&str, i64), ()>) {
+        assert_eq!(expected, signed::<i64>(input).map_err(|_| ()));
+    }
+
+    #[rstest]
+    #[case("255", Ok(("", 255)))]
+    #[case("256", Err(()))]
+    #[case("-1", Err(()))]
+    fn test_unsigned(#[case] input: &str, #[case] expected: Result<(&str, u8), ()>) {
+        assert_eq!(expected, unsigned::<u8>(input).map_err(|_| ()));
+    }
+
+    #[test]
+    fn test_xy() {
+        assert_eq!(Ok((" v", (2, -4))), xy::<i32>("2,-4 v"));
+        assert_eq!(Ok(("", IVec2::new(-3, 3))), ivec2("-3,3"));
+        assert!(ivec2("3").is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use aoc_parse::Grid;
+use glam::IVec2;
+
+/// The eight cells around one, clockwise from straight up
+pub const NEIGHBORS: [IVec2; 8] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+];
+
+/// One generation of a cellular automaton: each cell's next value from its own and
+/// its [`NEIGHBORS`], which are `None` off the edge of the grid
+pub fn step_grid<T>(grid: &Grid<T>, mut rule: impl FnMut(&T, [Option<&T>; 8]) -> T) -> Grid<T> {
+    Grid {
+        width: grid.width,
+        height: grid.height,
+        cells: grid
+            .positions()
+            .map(|(position, cell)| rule(cell, NEIGHBORS.map(|offset| grid.get(position + offset))))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_parse::char_grid;
+
+    use crate::{Cycle, Outcome, Simulation};
+
+    fn life(grid: &Grid<char>) -> Grid<char> {
+        step_grid(grid, |&cell, neighbors| {
+            match (cell, neighbors.iter().filter(|&&n| n == Some(&'#')).count()) {
+                ('#', 2 | 3) | (_, 3) => '#',
+                _ => '.',
+            }
+        })
+    }
+
+    fn grid(rows: &str) -> Grid<char> {
+        char_grid(rows).unwrap().1
+    }
+
+    #[test]
+    fn test_blinker() {
+        let mut simulation = Simulation::new(grid(".....\n..#..\n..#..\n..#..\n....."), |grid| Some(life(grid)));
+
+        assert_eq!(Outcome::Cycled(Cycle { start: 0, period: 2 }), simulation.run());
+        assert_eq!(Some(&grid(".....\n.....\n.###.\n.....\n.....")), simulation.state_at(99));
+    }
+
+    #[test]
+    fn test_glider_hits_the_wall() {
+        // without wrapping around, the glider ends up stuck as a block in the corner
+        let glider = grid(".#....\n..#...\n###...\n......\n......\n......");
+        let mut simulation = Simulation::new(glider, |grid| Some(life(grid)));
+
+        let Outcome::Cycled(cycle) = simulation.run() else {
+            panic!("a finite grid has to repeat");
+        };
+        assert_eq!(1, cycle.period);
+        assert_eq!(Some(&grid("......\n......\n......\n......\n....##\n....##")), simulation.state_at(1000));
+    }
+}
@@ -0,0 +1,12 @@
+//! Stepping a state over and over, whether that's a whole grid, a swarm of robots
+//! or a guard on patrol.
+//!
+//! A [`Simulation`] keeps every state it's been through, or just a hash of each,
+//! so it notices the first time one comes round again, and from then on knows any
+//! step without taking it.
+
+mod cells;
+mod simulation;
+
+pub use cells::{step_grid, NEIGHBORS};
+pub use simulation::{Cycle, Outcome, Simulation};
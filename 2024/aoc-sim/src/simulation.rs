@@ -0,0 +1,301 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash, RandomState},
+};
+
+/// From step `start` on, the states repeat every `period` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The step before the repeat starts that's in the same state as `step`
+    pub fn equivalent(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.period
+        }
+    }
+}
+
+/// Why a simulation stopped taking steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// the step function gave up after this many steps
+    Halted(usize),
+    Cycled(Cycle),
+}
+
+/// A state and the function taking it one step on, which says `None` once there's
+/// nowhere left to go (the guard walked off the map). Steps are only taken when
+/// something asks for a state that hasn't been seen yet.
+pub struct Simulation<S, F> {
+    step: F,
+    /// whether to keep every state, or only the start and the latest one
+    record: bool,
+    /// every state so far in order, the start first, when recording. Never holds the
+    /// same one twice. Otherwise just the start and the latest state.
+    history: Vec<S>,
+    /// how many steps it took to get to the latest state
+    steps: usize,
+    /// an earlier state worked out again from the start when not recording, and its step
+    replay: Option<(usize, S)>,
+    /// which steps had states with each hash, which saves keeping a second copy of
+    /// every state as a key
+    index: HashMap<u64, Vec<usize>>,
+    hasher: RandomState,
+    outcome: Option<Outcome>,
+}
+
+impl<S, F> Simulation<S, F>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> Option<S>,
+{
+    pub fn new(start: S, step: F) -> Self {
+        Self::with_recording(start, step, true)
+    }
+
+    /// Without `record` only the hash of each state is kept, and the steps from the
+    /// start are taken again whenever an earlier state is needed, like checking that
+    /// a state seen before really has come round again
+    pub fn with_recording(start: S, step: F, record: bool) -> Self {
+        let hasher = RandomState::new();
+        let index = HashMap::from([(hasher.hash_one(&start), vec![0])]);
+
+        Self {
+            step,
+            record,
+            history: vec![start],
+            steps: 0,
+            replay: None,
+            index,
+            hasher,
+            outcome: None,
+        }
+    }
+
+    /// The state after `step` steps, which it must already have got to
+    fn visit(&mut self, step: usize) -> &S {
+        if self.record || step == 0 {
+            return &self.history[step];
+        }
+        if step == self.steps {
+            return &self.history[1];
+        }
+
+        let (mut at, mut state) = match self.replay.take() {
+            Some((at, state)) if at <= step => (at, state),
+            _ => (0, self.history[0].clone()),
+        };
+        while at < step {
+            state = (self.step)(&state).expect("got further than this before");
+            at += 1;
+        }
+
+        &self.replay.insert((step, state)).1
+    }
+
+    /// Takes one more step, `false` if there's nothing new to find that way
+    fn advance(&mut self) -> bool {
+        if self.outcome.is_some() {
+            return false;
+        }
+
+        let Some(next) = (self.step)(&self.history[self.history.len() - 1]) else {
+            self.outcome = Some(Outcome::Halted(self.steps));
+            return false;
+        };
+
+        let hash = self.hasher.hash_one(&next);
+        let same_hash = self.index.get(&hash).cloned().unwrap_or_default();
+        if let Some(start) = same_hash.into_iter().find(|&step| *self.visit(step) == next) {
+            self.outcome = Some(Outcome::Cycled(Cycle { start, period: self.steps + 1 - start }));
+            return false;
+        }
+
+        self.steps += 1;
+        self.index.entry(hash).or_default().push(self.steps);
+        if self.record || self.steps == 1 {
+            self.history.push(next);
+        } else {
+            self.history[1] = next;
+        }
+        true
+    }
+
+    /// The state after `step` steps, `None` if it halts before then. Once there's
+    /// a cycle any step is known without taking it, however far ahead.
+    pub fn state_at(&mut self, step: usize) -> Option<&S> {
+        while self.steps < step && self.advance() {}
+
+        let step = match self.outcome {
+            Some(Outcome::Cycled(cycle)) => cycle.equivalent(step),
+            _ if step > self.steps => return None,
+            _ => step,
+        };
+        Some(self.visit(step))
+    }
+
+    /// Steps until it either halts or goes round in a cycle, which could be forever
+    /// for a state that keeps growing
+    pub fn run(&mut self) -> Outcome {
+        while self.advance() {}
+        self.outcome.expect("only stops advancing with an outcome")
+    }
+
+    /// The first step with a state `predicate` likes, `None` if it halts or starts
+    /// repeating itself without getting there
+    pub fn find(&mut self, predicate: impl FnMut(&S) -> bool) -> Option<usize> {
+        self.find_from(0, predicate)
+    }
+
+    /// Like [`Simulation::find`], only skipping the steps before `first`
+    pub fn find_from(&mut self, first: usize, mut predicate: impl FnMut(&S) -> bool) -> Option<usize> {
+        let mut step = first;
+        loop {
+            if predicate(self.state_at(step)?) {
+                return Some(step);
+            }
+            // a whole period since the repeats started is everything there is to see
+            if let Some(cycle) = self.cycle() {
+                if step + 1 >= first.max(cycle.start) + cycle.period {
+                    return None;
+                }
+            }
+            step += 1;
+        }
+    }
+
+    /// The states so far, each one once, for replaying or drawing. `None` when not
+    /// recording.
+    pub fn history(&self) -> Option<&[S]> {
+        self.record.then_some(&self.history)
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    pub fn cycle(&self) -> Option<Cycle> {
+        match self.outcome {
+            Some(Outcome::Cycled(cycle)) => Some(cycle),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 3)]
+    #[case(4, 4)]
+    #[case(10, 4)]
+    #[case(11, 5)]
+    fn test_equivalent(#[case] step: usize, #[case] expected: usize) {
+        assert_eq!(expected, Cycle { start: 4, period: 3 }.equivalent(step));
+    }
+
+    #[test]
+    fn test_finds_the_cycle() {
+        // n -> n² mod 21 from 2 goes 2, 4, 16, 4, ...
+        let mut simulation = Simulation::new(2_u64, |&n| Some(n * n % 21));
+
+        assert_eq!(Outcome::Cycled(Cycle { start: 1, period: 2 }), simulation.run());
+        assert_eq!(Some(&[2, 4, 16][..]), simulation.history());
+        assert_eq!(Some(&4), simulation.state_at(1_000_000_001));
+        assert_eq!(Some(&16), simulation.state_at(1_000_000_000));
+    }
+
+    #[test]
+    fn test_only_steps_when_asked() {
+        let mut steps = 0;
+        let mut simulation = Simulation::new(0_u32, |&n| {
+            steps += 1;
+            Some((n + 1) % 1000)
+        });
+
+        assert_eq!(Some(&7), simulation.state_at(7));
+        assert_eq!(Some(&3), simulation.state_at(3));
+        assert_eq!(Some(8), simulation.history().map(<[_]>::len));
+        assert_eq!(None, simulation.outcome());
+
+        assert_eq!(Some(&500), simulation.state_at(2500));
+        drop(simulation);
+        assert_eq!(1000, steps);
+    }
+
+    #[test]
+    fn test_halts() {
+        let mut simulation = Simulation::new(10_u32, |&n| n.checked_sub(3));
+
+        assert_eq!(Some(3), simulation.find(|&n| n < 3));
+        assert_eq!(None, simulation.state_at(4));
+        assert_eq!(Some(Outcome::Halted(3)), simulation.outcome());
+        assert_eq!(None, simulation.cycle());
+        assert_eq!(None, simulation.find(|&n| n == 0));
+    }
+
+    #[test]
+    fn test_find_gives_up_on_a_cycle() {
+        let mut simulation = Simulation::new(1_u32, |&n| Some(n * 3 % 7));
+
+        assert_eq!(Some(4), simulation.find(|&n| n == 4));
+        assert_eq!(None, simulation.find(|&n| n == 0));
+        assert_eq!(Some(Cycle { start: 0, period: 6 }), simulation.cycle());
+    }
+
+    #[test]
+    fn test_matches_stepping_by_hand() {
+        // n² + 7 mod 1009 wanders a while before it loops
+        let next = |&n: &u64| (n * n + 7) % 1009;
+        let mut simulation = Simulation::new(5, |n| Some(next(n)));
+        let cycle = match simulation.run() {
+            Outcome::Cycled(cycle) => cycle,
+            outcome => panic!("expected a cycle, got {outcome:?}"),
+        };
+
+        let mut n = 5;
+        for step in 0..5000 {
+            assert_eq!(Some(&n), simulation.state_at(step));
+            assert_eq!(simulation.history().unwrap()[cycle.equivalent(step)], n);
+            n = next(&n);
+        }
+    }
+
+    #[test]
+    fn test_without_recording() {
+        let next = |&n: &u64| (n * n + 7) % 1009;
+        let mut recorded = Simulation::new(5, |n| Some(next(n)));
+        let mut unrecorded = Simulation::with_recording(5, |n| Some(next(n)), false);
+
+        assert_eq!(recorded.run(), unrecorded.run());
+        assert_eq!(None, unrecorded.history());
+        for step in [0, 3, 2, 40, 5000, 1, 4999] {
+            assert_eq!(recorded.state_at(step).copied(), unrecorded.state_at(step).copied());
+        }
+        assert_eq!(recorded.find(|&n| n == 7), unrecorded.find(|&n| n == 7));
+    }
+
+    #[test]
+    fn test_find_from() {
+        let mut simulation = Simulation::with_recording(1_u32, |&n| Some(n * 3 % 7), false);
+
+        assert_eq!(Some(0), simulation.find(|&n| n == 1));
+        assert_eq!(Some(6), simulation.find_from(1, |&n| n == 1));
+        assert_eq!(Some(9), simulation.find_from(8, |&n| n == 6));
+        assert_eq!(None, simulation.find_from(3, |&n| n == 0));
+
+        // stuck from the start, so step 1 is the same as step 0
+        let mut stuck = Simulation::new(4_u32, |&n| Some(n));
+        assert_eq!(Some(1), stuck.find_from(1, |&n| n == 4));
+    }
+}
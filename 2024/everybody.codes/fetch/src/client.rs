@@ -0,0 +1,285 @@
+use std::{fs, path::Path};
+
+use serde_json::Value;
+
+use crate::{
+    custom_error::EcError,
+    notes::{self, Parts},
+};
+
+pub const API_URL: &str = "https://everybody.codes";
+pub const ASSETS_URL: &str = "https://everybody-codes.b-cdn.net";
+
+/// Where the seed and keys (api) and the encrypted notes (assets) come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    pub api: String,
+    pub assets: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            api: API_URL.to_string(),
+            assets: ASSETS_URL.to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Everything from one server, like a local stub
+    pub fn at(base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/').to_string();
+
+        Self {
+            api: base_url.clone(),
+            assets: base_url,
+        }
+    }
+
+    pub fn seed_url(&self) -> String {
+        format!("{}/api/user/me", self.api)
+    }
+
+    pub fn keys_url(&self, event: u32, quest: u32) -> String {
+        format!("{}/api/event/{event}/quest/{quest}", self.api)
+    }
+
+    pub fn notes_url(&self, event: u32, quest: u32, seed: u64) -> String {
+        format!("{}/assets/{event}/{quest}/input/{seed}.json", self.assets)
+    }
+}
+
+/// `quest-03` is quest 3
+pub fn quest_number(name: &str) -> miette::Result<u32, EcError> {
+    name.strip_prefix("quest-")
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| EcError::InvalidQuest(name.to_string()))
+}
+
+pub struct Client {
+    endpoints: Endpoints,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(endpoints: Endpoints, session: String) -> Self {
+        Self {
+            endpoints,
+            session,
+            agent: ureq::AgentBuilder::new().build(),
+        }
+    }
+
+    fn get_json(&self, url: &str) -> miette::Result<Value, EcError> {
+        let request_failed = |source| EcError::Request {
+            url: url.to_string(),
+            source: Box::new(source),
+        };
+        let unexpected = |reason: String| EcError::UnexpectedResponse {
+            url: url.to_string(),
+            reason,
+        };
+
+        let body = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("everybody-codes={}", self.session))
+            .call()
+            .map_err(request_failed)?
+            .into_string()?;
+
+        serde_json::from_str(&body).map_err(|error| unexpected(error.to_string()))
+    }
+
+    /// Every player gets their own notes, picked by this
+    pub fn seed(&self) -> miette::Result<u64, EcError> {
+        let url = self.endpoints.seed_url();
+
+        self.get_json(&url)?
+            .get("seed")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| EcError::UnexpectedResponse {
+                url,
+                reason: "no seed, is EC_SESSION still valid?".to_string(),
+            })
+    }
+
+    /// The decrypted input of each part that has been unlocked
+    pub fn inputs(&self, event: u32, quest: u32) -> miette::Result<Parts, EcError> {
+        let seed = self.seed()?;
+        let keys = notes::key_parts(&self.get_json(&self.endpoints.keys_url(event, quest))?);
+        let notes =
+            notes::note_parts(&self.get_json(&self.endpoints.notes_url(event, quest, seed))?);
+
+        let mut inputs = Parts::default();
+        for (index, (key, note)) in keys.iter().zip(&notes).enumerate() {
+            if let (Some(key), Some(note)) = (key, note) {
+                inputs[index] = Some(notes::decrypt(index as u8 + 1, key, note)?);
+            }
+        }
+
+        Ok(inputs)
+    }
+}
+
+/// Writes `input1.txt`..`input3.txt` for the parts there are inputs for,
+/// returning which parts were written
+pub fn write_inputs(quest_dir: &Path, inputs: &Parts) -> miette::Result<Vec<u8>, EcError> {
+    if !quest_dir.is_dir() {
+        return Err(EcError::MissingQuestDirectory(
+            quest_dir.display().to_string(),
+        ));
+    }
+
+    let mut written = Vec::new();
+    for (part, input) in (1..=3).zip(inputs) {
+        if let Some(input) = input {
+            fs::write(quest_dir.join(format!("input{part}.txt")), input)?;
+            written.push(part);
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use aoc_core::{Classify, ErrorKind};
+    use rstest::rstest;
+    use serde_json::json;
+
+    use crate::notes::tests::{encrypt, KEY};
+
+    /// Serves the three endpoints for event 2024 quest 3 and seed 7, with only
+    /// the first two parts unlocked
+    fn stub() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+
+                let mut authorised = false;
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    authorised |= header.eq_ignore_ascii_case("cookie: everybody-codes=secret\r\n");
+                    header.clear();
+                }
+
+                let body = match path.as_str() {
+                    _ if !authorised => None,
+                    "/api/user/me" => Some(json!({ "name": "someone", "seed": 7 })),
+                    "/api/event/2024/quest/3" => {
+                        Some(json!({ "key1": KEY, "key2": KEY.to_uppercase() }))
+                    }
+                    "/assets/2024/3/input/7.json" => Some(json!({
+                        "1": encrypt(KEY, "ABBAC"),
+                        "2": encrypt(&KEY.to_uppercase(), "AxBCDDCAxD"),
+                        "3": encrypt(&"locked".repeat(6)[..32], "xBxAAABCDxCC"),
+                    })),
+                    _ => None,
+                };
+
+                let (status, body) = match body {
+                    Some(body) => ("200 OK", body.to_string()),
+                    None => ("404 Not Found", String::new()),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        format!("http://{address}")
+    }
+
+    #[test]
+    fn test_inputs_from_stub() {
+        let client = Client::new(Endpoints::at(&stub()), "secret".to_string());
+
+        assert_eq!(7, client.seed().unwrap());
+        assert_eq!(
+            [
+                Some("ABBAC".to_string()),
+                Some("AxBCDDCAxD".to_string()),
+                None
+            ],
+            client.inputs(2024, 3).unwrap()
+        );
+        assert!(matches!(
+            client.inputs(2024, 4),
+            Err(EcError::Request { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bad_session() {
+        let client = Client::new(Endpoints::at(&stub()), "stale".to_string());
+
+        let error = client.seed().unwrap_err();
+        assert!(matches!(error, EcError::Request { .. }));
+        assert_eq!(ErrorKind::Io, error.kind());
+    }
+
+    #[test]
+    fn test_write_inputs() {
+        let dir = std::env::temp_dir().join(format!("fetch-test-{}", std::process::id()));
+        let inputs = [Some("ABBAC".to_string()), None, Some("xBx".to_string())];
+
+        let error = write_inputs(&dir, &inputs).unwrap_err();
+        assert!(matches!(error, EcError::MissingQuestDirectory(_)));
+        assert_eq!(ErrorKind::InvalidInput, error.kind());
+
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(vec![1, 3], write_inputs(&dir, &inputs).unwrap());
+        assert_eq!("ABBAC", fs::read_to_string(dir.join("input1.txt")).unwrap());
+        assert!(!dir.join("input2.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[rstest]
+    #[case("quest-03", Some(3))]
+    #[case("quest-12", Some(12))]
+    #[case("day-03", None)]
+    #[case("quest-", None)]
+    fn test_quest_number(#[case] name: &str, #[case] expected: Option<u32>) {
+        assert_eq!(expected, quest_number(name).ok());
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let endpoints = Endpoints::at("http://localhost:8080/");
+
+        assert_eq!(
+            "http://localhost:8080/api/event/2024/quest/3",
+            endpoints.keys_url(2024, 3)
+        );
+        assert_eq!(
+            "https://everybody-codes.b-cdn.net/assets/2024/3/input/7.json",
+            Endpoints::default().notes_url(2024, 3, 7)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use aoc_core::{Classify, ErrorKind};
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum EcError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("request to {url} failed")]
+    #[diagnostic(code(aoc::request_failed))]
+    Request {
+        url: String,
+        #[source]
+        source: Box<ureq::Error>,
+    },
+
+    #[error("unexpected response from {url}: {reason}")]
+    #[diagnostic(code(aoc::unexpected_response))]
+    UnexpectedResponse { url: String, reason: String },
+
+    #[error("note for part {part} could not be decrypted: {reason}")]
+    #[diagnostic(code(aoc::decryption_failed))]
+    Decryption { part: u8, reason: String },
+
+    #[error("{0:?} is not a quest, expected something like `quest-03`")]
+    #[diagnostic(code(aoc::invalid_quest))]
+    InvalidQuest(String),
+
+    #[error("{0} doesn't exist")]
+    #[diagnostic(
+        code(aoc::missing_quest_directory),
+        help("create it first with `just create <quest>`")
+    )]
+    MissingQuestDirectory(String),
+
+    #[error("EC_SESSION is not set")]
+    #[diagnostic(
+        code(aoc::missing_session),
+        help("put the everybody-codes cookie in .env as EC_SESSION=...")
+    )]
+    MissingSession,
+}
+
+impl From<std::io::Error> for EcError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Core(error.into())
+    }
+}
+
+impl Classify for EcError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::Request { .. } | Self::UnexpectedResponse { .. } => ErrorKind::Io,
+            Self::Decryption { .. } => ErrorKind::Parse,
+            Self::InvalidQuest(..) | Self::MissingQuestDirectory(..) | Self::MissingSession => ErrorKind::InvalidInput,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod client;
+pub mod custom_error;
+pub mod notes;
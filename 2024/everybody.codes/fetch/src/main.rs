@@ -0,0 +1,60 @@
+use fetch::{
+    client::{quest_number, write_inputs, Client, Endpoints},
+    custom_error::EcError,
+};
+use std::path::Path;
+
+/// `fetch quest-03 [--event 2024] [--base-url http://localhost:8080]`
+///
+/// Needs `EC_SESSION` (the `everybody-codes` cookie) in the environment or `.env`.
+/// `EC_BASE_URL` does the same as `--base-url`, for pointing everything at a stub.
+fn main() -> miette::Result<()> {
+    // Load .env file from the current directory or its parents
+    dotenvy::dotenv().ok();
+
+    let mut args = std::env::args().skip(1);
+    let mut quest = None;
+    let mut event = 2024;
+    let mut base_url = std::env::var("EC_BASE_URL").ok();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--event" => {
+                event = args
+                    .next()
+                    .and_then(|event| event.parse().ok())
+                    .ok_or_else(|| miette::miette!("--event needs a year"))?;
+            }
+            "--base-url" => {
+                base_url = Some(
+                    args.next()
+                        .ok_or_else(|| miette::miette!("--base-url needs a url"))?,
+                );
+            }
+            _ => quest = Some(arg),
+        }
+    }
+    let quest = quest
+        .ok_or_else(|| miette::miette!("usage: fetch quest-03 [--event 2024] [--base-url URL]"))?;
+
+    let session = std::env::var("EC_SESSION").map_err(|_| EcError::MissingSession)?;
+    let endpoints = base_url
+        .as_deref()
+        .map_or_else(Endpoints::default, Endpoints::at);
+    let client = Client::new(endpoints, session);
+
+    let inputs = client.inputs(event, quest_number(&quest)?)?;
+    let quest_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(&quest);
+    let written = write_inputs(&quest_dir, &inputs)?;
+
+    for part in 1..=3 {
+        if written.contains(&part) {
+            println!("{quest} part{part}  written to input{part}.txt");
+        } else {
+            println!("{quest} part{part}  still locked");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use aes::{
+    cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit},
+    Aes256,
+};
+use serde_json::Value;
+
+use crate::custom_error::EcError;
+
+type Decryptor = cbc::Decryptor<Aes256>;
+
+/// One value per part, missing for parts that aren't unlocked yet
+pub type Parts = [Option<String>; 3];
+
+/// Picks the three parts out of a JSON object, with keys named by `name(part)`
+pub fn parts(json: &Value, name: impl Fn(u8) -> String) -> Parts {
+    [1, 2, 3].map(|part| {
+        json.get(name(part))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    })
+}
+
+/// The encrypted notes are keyed `"1"` to `"3"`
+pub fn note_parts(json: &Value) -> Parts {
+    parts(json, |part| part.to_string())
+}
+
+/// The keys are `"key1"` to `"key3"`
+pub fn key_parts(json: &Value) -> Parts {
+    parts(json, |part| format!("key{part}"))
+}
+
+/// Notes are hex encoded AES-256-CBC with PKCS#7 padding. The key is the 32
+/// characters given for the part and the IV its first 16.
+pub fn decrypt(part: u8, key: &str, note: &str) -> miette::Result<String, EcError> {
+    let failed = |reason: String| EcError::Decryption { part, reason };
+
+    let key = key.as_bytes();
+    if key.len() != 32 {
+        return Err(failed(format!("key is {} bytes, not 32", key.len())));
+    }
+    let ciphertext = hex::decode(note.trim()).map_err(|error| failed(error.to_string()))?;
+
+    let plaintext = Decryptor::new(key.into(), key[..16].into())
+        .decrypt_padded_vec_mut::<Pkcs7>(&ciphertext)
+        .map_err(|_| failed("wrong key or corrupted note".to_string()))?;
+
+    String::from_utf8(plaintext).map_err(|error| failed(error.to_string()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use aes::cipher::BlockEncryptMut;
+    use serde_json::json;
+
+    pub(crate) const KEY: &str = "0123456789abcdefghijklmnopqrstuv";
+
+    /// What the site does to the notes before serving them
+    pub(crate) fn encrypt(key: &str, plaintext: &str) -> String {
+        let key = key.as_bytes();
+        let ciphertext = cbc::Encryptor::<Aes256>::new(key.into(), key[..16].into())
+            .encrypt_padded_vec_mut::<Pkcs7>(plaintext.as_bytes());
+
+        hex::encode(ciphertext)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for plaintext in ["", "ABBAC", &"xBxAAABCDxCC\n".repeat(100)] {
+            assert_eq!(
+                plaintext,
+                decrypt(1, KEY, &encrypt(KEY, plaintext)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrong_key() {
+        let note = encrypt(KEY, "ABBAC");
+
+        assert!(matches!(
+            decrypt(2, "short", &note),
+            Err(EcError::Decryption { part: 2, .. })
+        ));
+        assert!(matches!(
+            decrypt(1, KEY, "not hex"),
+            Err(EcError::Decryption { part: 1, .. })
+        ));
+        // a wrong key leaves the padding broken or the text garbled
+        assert!(decrypt(3, &KEY.to_uppercase(), &note).map_or(true, |text| text != "ABBAC"));
+    }
+
+    #[test]
+    fn test_parts() {
+        let notes = json!({ "1": "aa", "2": "bb", "3": "cc" });
+        let keys = json!({ "key1": "k", "key2": null });
+
+        assert_eq!(
+            [
+                Some("aa".to_string()),
+                Some("bb".to_string()),
+                Some("cc".to_string())
+            ],
+            note_parts(&notes)
+        );
+        assert_eq!([Some("k".to_string()), None, None], key_parts(&keys));
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::custom_error::EcError;
+
+/// A slot in a group with no creature in it
+pub const EMPTY: char = 'x';
+
+/// How many potions each kind of creature takes on its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Potions {
+    table: HashMap<char, u32>,
+}
+
+impl Default for Potions {
+    /// Ancient Ants, Badass Beetles, Creepy Cockroaches and Diabolical Dragonflies
+    fn default() -> Self {
+        Self::new([('A', 0), ('B', 1), ('C', 3), ('D', 5)])
+    }
+}
+
+impl Potions {
+    /// Creatures are case insensitive, so the table is keyed by uppercase letters
+    pub fn new(table: impl IntoIterator<Item = (char, u32)>) -> Self {
+        Self {
+            table: table
+                .into_iter()
+                .map(|(creature, potions)| (creature.to_ascii_uppercase(), potions))
+                .collect(),
+        }
+    }
+
+    pub fn for_creature(&self, creature: char) -> miette::Result<u32, EcError> {
+        self.table
+            .get(&creature.to_ascii_uppercase())
+            .copied()
+            .ok_or(EcError::UnknownCreature(creature))
+    }
+
+    /// Creatures come in groups of `group_size`, the last one possibly smaller.
+    /// Fighting alongside others makes every creature in a group need one more
+    /// potion for each other creature there, empty slots don't count.
+    pub fn battle(&self, input: &str, group_size: usize) -> miette::Result<u32, EcError> {
+        let creatures = input.trim().chars().collect::<Vec<_>>();
+
+        creatures
+            .chunks(group_size.max(1))
+            .map(|group| {
+                let present = group.iter().filter(|&&creature| creature != EMPTY).count() as u32;
+                let bonus = present.saturating_sub(1);
+
+                group
+                    .iter()
+                    .filter(|&&creature| creature != EMPTY)
+                    .map(|&creature| Ok(self.for_creature(creature)? + bonus))
+                    .sum::<Result<u32, EcError>>()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("ABBAC", 1, 5)]
+    #[case("AxBCDDCAxD", 2, 28)]
+    #[case("xBxAAABCDxCC", 3, 30)]
+    #[case("xBxAAABCDxC", 3, 25)]
+    #[case("ABCD", 4, 21)]
+    #[case("AxxD", 4, 7)]
+    #[case("", 3, 0)]
+    fn test_battle(#[case] input: &str, #[case] group_size: usize, #[case] expected: u32) {
+        assert_eq!(
+            expected,
+            Potions::default().battle(input, group_size).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_custom_table() {
+        let potions = Potions::new([('a', 2), ('E', 7)]);
+
+        assert_eq!(2 + 1 + 7 + 1, potions.battle("ae", 2).unwrap());
+        assert!(matches!(
+            potions.battle("AB", 2),
+            Err(EcError::UnknownCreature('B'))
+        ));
+    }
+}
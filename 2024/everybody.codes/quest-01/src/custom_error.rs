@@ -1,9 +1,23 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum EcError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("no potion count for creature {0:?}")]
+    #[diagnostic(code(aoc::unknown_creature))]
+    UnknownCreature(char),
+}
+
+impl Classify for EcError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::UnknownCreature(..) => ErrorKind::InvalidInput,
+        }
+    }
 }
@@ -1,10 +1,8 @@
-use crate::custom_error::EcError;
-
-use super::hit_count;
+use crate::{battle::Potions, custom_error::EcError};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, EcError> {
-    Ok(input.chars().map(hit_count).sum::<u32>().to_string())
+    Ok(Potions::default().battle(input, 1)?.to_string())
 }
 
 #[cfg(test)]
@@ -0,0 +1,24 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum EcError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("line {0} of the answer ledger should be `<puzzle> <part> <answer>`")]
+    #[diagnostic(code(aoc::invalid_ledger_line))]
+    InvalidLedgerLine(usize),
+
+    #[error("unknown argument {0:?}")]
+    #[diagnostic(
+        code(aoc::invalid_argument),
+        help("usage: runner [--record] [--check] [quest-NN[/partN]]...")
+    )]
+    InvalidArgument(String),
+
+    #[error("{0} answers don't match the ledger")]
+    #[diagnostic(code(aoc::wrong_answers))]
+    WrongAnswers(usize),
+}
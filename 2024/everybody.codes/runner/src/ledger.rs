@@ -0,0 +1,152 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use crate::custom_error::EcError;
+
+/// Known good answers, one per line as `<puzzle> <part> <answer>`:
+///
+/// ```text
+/// # comments and blank lines are ignored
+/// quest-01 part1 1319
+/// day-01 part2 31
+/// ```
+///
+/// Puzzles are just directory names, so the same file works for Advent days.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger {
+    answers: BTreeMap<(String, u8), String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// nothing in the ledger yet
+    Unrecorded,
+}
+
+impl Ledger {
+    pub fn parse(input: &str) -> miette::Result<Self, EcError> {
+        let mut answers = BTreeMap::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || EcError::InvalidLedgerLine(index + 1);
+            let mut fields = line.split_whitespace();
+            let (Some(puzzle), Some(part)) = (fields.next(), fields.next()) else {
+                return Err(invalid());
+            };
+            let answer = fields.collect::<Vec<_>>().join(" ");
+            if answer.is_empty() {
+                return Err(invalid());
+            }
+            let part = part
+                .strip_prefix("part")
+                .and_then(|part| part.parse().ok())
+                .ok_or_else(invalid)?;
+
+            answers.insert((puzzle.to_string(), part), answer);
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// An empty ledger if the file doesn't exist yet
+    pub fn load(path: &Path) -> miette::Result<Self, EcError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<(), EcError> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    pub fn get(&self, puzzle: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(puzzle.to_string(), part))
+            .map(String::as_str)
+    }
+
+    pub fn record(&mut self, puzzle: &str, part: u8, answer: &str) {
+        self.answers
+            .insert((puzzle.to_string(), part), answer.to_string());
+    }
+
+    pub fn check(&self, puzzle: &str, part: u8, answer: &str) -> Verdict {
+        match self.get(puzzle, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unrecorded,
+        }
+    }
+}
+
+impl fmt::Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ((puzzle, part), answer) in &self.answers {
+            writeln!(f, "{puzzle} part{part} {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse() {
+        let ledger = Ledger::parse(
+            "# quest 1
+quest-01 part1 1319
+
+quest-01  part2   5 6
+day-01 part2 31",
+        )
+        .unwrap();
+
+        assert_eq!(Some("1319"), ledger.get("quest-01", 1));
+        assert_eq!(Some("5 6"), ledger.get("quest-01", 2));
+        assert_eq!(Some("31"), ledger.get("day-01", 2));
+        assert_eq!(None, ledger.get("quest-01", 3));
+    }
+
+    #[rstest]
+    #[case("quest-01 part1", 1)]
+    #[case("quest-01 1 5", 1)]
+    #[case("# fine\nquest-01 partx 5", 2)]
+    fn test_invalid(#[case] input: &str, #[case] line: usize) {
+        assert!(matches!(Ledger::parse(input), Err(EcError::InvalidLedgerLine(l)) if l == line));
+    }
+
+    #[test]
+    fn test_check_and_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.record("quest-02", 1, "42");
+        ledger.record("quest-01", 3, "7");
+
+        assert_eq!(Verdict::Correct, ledger.check("quest-02", 1, "42"));
+        assert_eq!(
+            Verdict::Wrong {
+                expected: "42".to_string()
+            },
+            ledger.check("quest-02", 1, "41")
+        );
+        assert_eq!(Verdict::Unrecorded, ledger.check("quest-02", 2, "41"));
+
+        assert_eq!("quest-01 part3 7\nquest-02 part1 42\n", ledger.to_string());
+        assert_eq!(ledger, Ledger::parse(&ledger.to_string()).unwrap());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod custom_error;
+
+pub mod ledger;
+pub mod quests;
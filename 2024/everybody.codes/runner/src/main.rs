@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use runner::{
+    custom_error::EcError,
+    ledger::{Ledger, Verdict},
+    quests::{self, Outcome},
+};
+
+/// Runs every part of every quest, or only the ones named like `quest-01` or
+/// `quest-01/part2`.
+///
+/// `--check` fails if any answer disagrees with `answers.txt`,
+/// `--record` writes new and changed answers into it.
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let mut check = false;
+    let mut record = false;
+    let mut filters = Vec::new();
+    for argument in std::env::args().skip(1) {
+        match argument.as_str() {
+            "--check" => check = true,
+            "--record" => record = true,
+            filter if filter.starts_with("quest-") => filters.push(filter.to_string()),
+            _ => return Err(EcError::InvalidArgument(argument).into()),
+        }
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let ledger_path = root.join("answers.txt");
+    let mut ledger = Ledger::load(&ledger_path)?;
+    let selected = |name: &str| filters.is_empty() || filters.iter().any(|filter| filter == name);
+
+    let mut wrong = 0;
+    for name in quests::discover(&root).map_err(EcError::from)? {
+        let Some(quest) = quests::find(&name) else {
+            if selected(&name) {
+                println!("{name}  not linked into the runner");
+            }
+            continue;
+        };
+
+        for part in 1..=3 {
+            if !selected(&name) && !selected(&format!("{name}/part{part}")) {
+                continue;
+            }
+
+            print!("{name} part{part}  ");
+            match quests::run(&root, quest, part) {
+                Outcome::Solved { answer, elapsed } => {
+                    let verdict = match ledger.check(&name, part, &answer) {
+                        Verdict::Correct => "ok".to_string(),
+                        Verdict::Wrong { expected } => {
+                            wrong += 1;
+                            format!("WRONG, expected {expected}")
+                        }
+                        Verdict::Unrecorded => "new".to_string(),
+                    };
+                    println!("{answer:<20} {:>12}  {verdict}", format!("{elapsed:.2?}"));
+
+                    if record {
+                        ledger.record(&name, part, &answer);
+                    }
+                }
+                Outcome::Failed { kind, report } => {
+                    wrong += 1;
+                    println!("failed ({kind})");
+                    eprintln!("{report:?}");
+                }
+                Outcome::NoInput => println!("no input"),
+            }
+        }
+    }
+
+    if record {
+        ledger.save(&ledger_path)?;
+    }
+    if check && !record && wrong > 0 {
+        return Err(EcError::WrongAnswers(wrong).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_core::{Classify, ErrorKind};
+
+/// Failures come back already classified, the report alone doesn't say what kind it is
+pub type Solver = fn(&str) -> Result<String, (ErrorKind, miette::Report)>;
+
+/// A quest crate linked into the runner
+#[derive(Debug, Clone, Copy)]
+pub struct Quest {
+    /// the directory name, `quest-01`
+    pub name: &'static str,
+    pub parts: [Solver; 3],
+}
+
+macro_rules! quest {
+    ($name:literal, $quest:ident) => {
+        Quest {
+            name: $name,
+            parts: [
+                |input| $quest::part1::process(input).map_err(|error| (error.kind(), error.into())),
+                |input| $quest::part2::process(input).map_err(|error| (error.kind(), error.into())),
+                |input| $quest::part3::process(input).map_err(|error| (error.kind(), error.into())),
+            ],
+        }
+    };
+}
+
+/// New quests go here as well as in the runner's `Cargo.toml`
+pub const QUESTS: &[Quest] = &[quest!("quest-01", quest_01), quest!("quest-02", quest_02)];
+
+pub fn find(name: &str) -> Option<&'static Quest> {
+    QUESTS.iter().find(|quest| quest.name == name)
+}
+
+/// The `quest-*` directories in the workspace, in order
+pub fn discover(root: &Path) -> io::Result<Vec<String>> {
+    let mut names = fs::read_dir(root)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with("quest-"))
+        .collect::<Vec<_>>();
+
+    names.sort();
+    Ok(names)
+}
+
+/// Each part reads its own `input1.txt`..`input3.txt`
+pub fn input_path(root: &Path, quest: &str, part: u8) -> PathBuf {
+    root.join(quest).join(format!("input{part}.txt"))
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Solved {
+        answer: String,
+        elapsed: Duration,
+    },
+    Failed {
+        kind: ErrorKind,
+        report: miette::Report,
+    },
+    /// the input file is missing or empty
+    NoInput,
+}
+
+pub fn run(root: &Path, quest: &Quest, part: u8) -> Outcome {
+    let input = match fs::read_to_string(input_path(root, quest.name, part)) {
+        Ok(input) if !input.trim().is_empty() => input,
+        _ => return Outcome::NoInput,
+    };
+
+    let start = Instant::now();
+    let result = quest.parts[part as usize - 1](&input);
+    let elapsed = start.elapsed();
+
+    match result {
+        Ok(answer) => Outcome::Solved { answer, elapsed },
+        Err((kind, report)) => Outcome::Failed { kind, report },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+    }
+
+    #[test]
+    fn test_every_quest_is_registered() {
+        let discovered = discover(&root()).unwrap();
+
+        assert_eq!(
+            discovered,
+            QUESTS.iter().map(|quest| quest.name).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let quest = find("quest-01").unwrap();
+
+        assert!(matches!(run(&root(), quest, 1), Outcome::Solved { .. }));
+        assert!(find("quest-99").is_none());
+    }
+
+    #[test]
+    fn test_failures_are_classified() {
+        let stuck = Quest {
+            name: "quest-01",
+            parts: [|_| Err((ErrorKind::NoSolution, miette::miette!("stuck"))); 3],
+        };
+
+        assert!(matches!(
+            run(&root(), &stuck, 2),
+            Outcome::Failed {
+                kind: ErrorKind::NoSolution,
+                ..
+            }
+        ));
+    }
+}
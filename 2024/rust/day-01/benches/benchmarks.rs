@@ -1,3 +1,4 @@
+use aoc_core::testing::Numbers;
 use day_01::*;
 
 fn main() {
@@ -14,3 +15,28 @@ fn part1() {
 fn part2() {
     part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
 }
+
+/// A thousand lines of five digit ids like the real input, so the parsers can be
+/// compared without it
+fn generated_input() -> String {
+    let mut numbers = Numbers::new(0x2024);
+    let mut id = move || numbers.between(10_000, 99_999);
+
+    (0..1000).map(|_| format!("{}   {}\n", id(), id())).collect()
+}
+
+mod parse {
+    use super::*;
+
+    #[divan::bench]
+    fn nom(bencher: divan::Bencher) {
+        let input = generated_input();
+        bencher.bench(|| part1_nom::parse(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn bytes(bencher: divan::Bencher) {
+        let input = generated_input();
+        bencher.bench(|| lists::unzip(divan::black_box(&input)).unwrap());
+    }
+}
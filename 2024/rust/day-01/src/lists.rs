@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::custom_error::AocError;
+
+/// Location ids in the puzzle input have five digits. Anything up to this fits in a
+/// counting table small enough to be worth it, bigger ids fall back to a comparison sort.
+pub const MAX_ID: u32 = 99_999;
+
+/// The two columns of the input, read a pair at a time straight from the bytes
+/// without allocating
+#[derive(Debug, Clone)]
+pub struct Pairs<'a> {
+    input: &'a str,
+    bytes: &'a [u8],
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Pairs<'a> {
+    fn skip(&mut self, whitespace: impl Fn(u8) -> bool) -> usize {
+        let start = self.position;
+        while let Some(&byte) = self.bytes.get(self.position) {
+            if !whitespace(byte) {
+                break;
+            }
+            if byte == b'\n' {
+                self.line += 1;
+            }
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let start = self.position;
+        let mut value: u32 = 0;
+        while let Some(&byte) = self.bytes.get(self.position).filter(|byte| byte.is_ascii_digit()) {
+            value = value.checked_mul(10)?.checked_add((byte - b'0') as u32)?;
+            self.position += 1;
+        }
+        (self.position > start).then_some(value)
+    }
+
+    fn pair(&mut self) -> Option<(u32, u32)> {
+        let left = self.number()?;
+        if self.skip(|byte| byte == b' ' || byte == b'\t') == 0 {
+            return None;
+        }
+        let right = self.number()?;
+        self.skip(|byte| byte == b' ' || byte == b'\t' || byte == b'\r');
+
+        match self.bytes.get(self.position) {
+            None | Some(b'\n') => Some((left, right)),
+            Some(_) => None,
+        }
+    }
+}
+
+impl Iterator for Pairs<'_> {
+    type Item = Result<(u32, u32), AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // blank lines and indentation are fine
+        self.skip(|byte| byte.is_ascii_whitespace());
+        if self.position >= self.bytes.len() {
+            return None;
+        }
+
+        let line = self.line;
+        match self.pair() {
+            Some(pair) => Some(Ok(pair)),
+            None => {
+                let length = self.input[self.position..].chars().next().map_or(0, char::len_utf8);
+                let error = AocError::parse(
+                    self.input,
+                    (self.position, length),
+                    format!("line {line} is not two ids separated by whitespace"),
+                );
+
+                // give up on the rest, the iterator is fused from here
+                self.position = self.bytes.len();
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+pub fn pairs(input: &str) -> Pairs<'_> {
+    Pairs {
+        input,
+        bytes: input.as_bytes(),
+        position: 0,
+        line: 1,
+    }
+}
+
+/// Both columns as lists
+pub fn unzip(input: &str) -> miette::Result<(Vec<u32>, Vec<u32>), AocError> {
+    pairs(input).collect()
+}
+
+/// Counting sort when every id is at most [`MAX_ID`], otherwise an ordinary sort
+pub fn sort_ids(ids: &mut [u32]) {
+    let Some(&max) = ids.iter().max() else {
+        return;
+    };
+    if max > MAX_ID {
+        ids.sort_unstable();
+        return;
+    }
+
+    let mut counts = vec![0_u32; max as usize + 1];
+    for &id in ids.iter() {
+        counts[id as usize] += 1;
+    }
+
+    let mut position = 0;
+    for (id, &count) in counts.iter().enumerate() {
+        ids[position..position + count as usize].fill(id as u32);
+        position += count as usize;
+    }
+}
+
+/// Sum of the differences between the smallest ids, the second smallest and so on
+pub fn distance(left: &mut [u32], right: &mut [u32]) -> u64 {
+    sort_ids(left);
+    sort_ids(right);
+
+    left.iter()
+        .zip(right.iter())
+        .map(|(l, r)| l.abs_diff(*r) as u64)
+        .sum()
+}
+
+/// Each id on the left times how often it appears on the right, from a single
+/// frequency table of the right list
+pub fn similarity(left: &[u32], right: &[u32]) -> u64 {
+    let score = |frequency: &dyn Fn(u32) -> u32| -> u64 {
+        left.iter()
+            .map(|&id| id as u64 * frequency(id) as u64)
+            .sum()
+    };
+
+    let max = right.iter().max().copied().unwrap_or(0);
+    if max <= MAX_ID {
+        let mut counts = vec![0_u32; max as usize + 1];
+        for &id in right {
+            counts[id as usize] += 1;
+        }
+        score(&|id| counts.get(id as usize).copied().unwrap_or(0))
+    } else {
+        let mut counts = HashMap::<u32, u32>::new();
+        for &id in right {
+            *counts.entry(id).or_default() += 1;
+        }
+        score(&|id| counts.get(&id).copied().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn test_unzip() {
+        assert_eq!(
+            (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]),
+            unzip(EXAMPLE).unwrap()
+        );
+        assert_eq!(
+            (vec![12, 5], vec![34, 6]),
+            unzip("\r\n   12\t34 \r\n\n5 6").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("3   4\n4\n", 2, 7)]
+    #[case("3   4\n4 x\n", 2, 8)]
+    #[case("3   4\n\n4 5 6\n", 3, 11)]
+    #[case("34", 1, 2)]
+    #[case("-3 4", 1, 0)]
+    #[case("99999999999 4", 1, 9)]
+    fn test_invalid_line(#[case] input: &str, #[case] line: usize, #[case] offset: usize) {
+        let Err(AocError::ParseError { message, span, .. }) = unzip(input) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(offset, span.offset());
+        assert!(message.starts_with(&format!("line {line} ")), "{message}");
+    }
+
+    #[rstest]
+    #[case(vec![3, 1, 2, 3, 0])]
+    #[case(vec![99_999, 5, 99_999, 10_000])]
+    #[case(vec![100_000, 7, 4_000_000_000, 7])]
+    #[case(vec![])]
+    fn test_sort_ids(#[case] mut ids: Vec<u32>) {
+        let mut expected = ids.clone();
+        expected.sort();
+
+        sort_ids(&mut ids);
+        assert_eq!(expected, ids);
+    }
+
+    #[test]
+    fn test_example() {
+        let (mut left, mut right) = unzip(EXAMPLE).unwrap();
+
+        assert_eq!(31, similarity(&left, &right));
+        assert_eq!(11, distance(&mut left, &mut right));
+    }
+
+    #[test]
+    fn test_similarity_large_ids() {
+        assert_eq!(
+            2 * 123_456 + 5,
+            similarity(&[123_456, 5, 8], &[123_456, 123_456, 5])
+        );
+    }
+}
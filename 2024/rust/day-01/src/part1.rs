@@ -1,19 +1,13 @@
-use crate::{custom_error::AocError, unzip};
+use crate::{
+    custom_error::AocError,
+    lists::{distance, unzip},
+};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (mut left, mut right): (Vec<i32>, Vec<i32>) = unzip(input);
+    let (mut left, mut right) = unzip(input)?;
 
-    left.sort();
-    right.sort();
-
-    let output = left
-        .iter()
-        .zip(right.iter())
-        .map(|(l, r)| (l - r).abs())
-        .sum::<i32>();
-
-    Ok(output.to_string())
+    Ok(distance(&mut left, &mut right).to_string())
 }
 
 #[cfg(test)]
@@ -1,8 +1,19 @@
 use miette::miette;
-use nom::IResult;
+use nom::{
+    IResult,
+    character::complete::{i32, line_ending, multispace0, space0, space1},
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair},
+};
 
-fn parse(input: &str) -> IResult<&str, (Vec<i32>, Vec<i32>)> {
-    todo!()
+pub fn parse(input: &str) -> IResult<&str, (Vec<i32>, Vec<i32>)> {
+    let (input, pairs) = delimited(
+        multispace0,
+        separated_list1(line_ending, preceded(space0, separated_pair(i32, space1, i32))),
+        multispace0,
+    )(input)?;
+
+    Ok((input, pairs.into_iter().unzip()))
 }
 
 pub fn process(input: &str) -> miette::Result<String> {
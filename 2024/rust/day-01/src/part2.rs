@@ -1,15 +1,13 @@
-use crate::{custom_error::AocError, unzip};
+use crate::{
+    custom_error::AocError,
+    lists::{similarity, unzip},
+};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (left, right): (Vec<i32>, Vec<i32>) = unzip(input);
+    let (left, right) = unzip(input)?;
 
-    let output: i32 = left
-        .iter()
-        .map(|n| n * right.iter().filter(|&x| x == n).count() as i32)
-        .sum();
-
-    Ok(output.to_string())
+    Ok(similarity(&left, &right).to_string())
 }
 
 #[cfg(test)]
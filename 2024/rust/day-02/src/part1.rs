@@ -1,10 +1,14 @@
-use crate::{custom_error::AocError, is_safe, line_to_nums};
+use crate::{
+    custom_error::AocError,
+    safety::{Tolerance, parse_reports},
+};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let output = input
-        .lines()
-        .filter(|line: &&str| is_safe(line_to_nums(line)))
+    let tolerance = Tolerance::default();
+    let output = parse_reports(input)?
+        .iter()
+        .filter(|levels| tolerance.is_safe(levels))
         .count();
 
     Ok(output.to_string())
@@ -1,46 +1,17 @@
-use crate::{custom_error::AocError, is_safe, line_to_nums};
+use crate::{
+    custom_error::AocError,
+    safety::{Tolerance, parse_reports},
+};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (safe, notsafe): (Vec<_>, Vec<_>) = input
-        .lines()
-        .enumerate()
-        .partition(|(_idx, line)| is_safe(line_to_nums(line)));
-
-    // dbg!(&safe, &notsafe);
-
-    let dampened = notsafe
+    let tolerance = Tolerance::default();
+    let output = parse_reports(input)?
         .iter()
-        // .filter(|(_idx, line)| {
-        //     // dbg!(idx, line);
-        //     let nums = line_to_nums(line);
-        //     for index in 0..nums.len() {
-        //         let mut new_nums = nums.clone();
-        //         new_nums.remove(index);
-        //         if is_safe(new_nums) {
-        //             return true;
-        //         } else {
-        //             continue;
-        //         }
-        //     }
-        //     false
-        // })
-        .filter(|(_idx, line)| {
-            let nums = line_to_nums(line);
-            // Check if removing any single number makes the sequence safe
-            nums.iter().enumerate().any(|(skip_idx, _)| {
-                is_safe(
-                    nums.iter()
-                        .enumerate()
-                        .filter(|(i, _)| *i != skip_idx)
-                        .map(|(_, &n)| n)
-                        .collect(),
-                )
-            })
-        })
+        .filter(|levels| tolerance.dampen(levels, 1).is_some())
         .count();
 
-    Ok((safe.len() + dampened).to_string())
+    Ok(output.to_string())
 }
 
 #[cfg(test)]
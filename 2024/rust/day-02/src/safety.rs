@@ -0,0 +1,249 @@
+use crate::custom_error::AocError;
+
+pub fn parse_reports(input: &str) -> miette::Result<Vec<Vec<i32>>, AocError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_whitespace()
+                .map(|level| {
+                    level
+                        .parse()
+                        .map_err(|_| AocError::parse_at(input, level, format!("level {level:?} is not a number")))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// How far apart adjacent levels may be, in the direction the report is going
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tolerance {
+    pub min_step: i32,
+    pub max_step: i32,
+}
+
+impl Default for Tolerance {
+    /// At least one and at most three, as the engineers want
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+        }
+    }
+}
+
+impl Tolerance {
+    /// `direction` is 1 for increasing and -1 for decreasing
+    fn step_ok(&self, direction: i32, from: i32, to: i32) -> bool {
+        (self.min_step..=self.max_step).contains(&(direction * (to - from)))
+    }
+
+    /// All increasing or all decreasing, with every step in range.
+    /// Reports with fewer than two levels are trivially safe.
+    pub fn is_safe(&self, levels: &[i32]) -> bool {
+        [1, -1].into_iter().any(|direction| {
+            levels
+                .windows(2)
+                .all(|pair| self.step_ok(direction, pair[0], pair[1]))
+        })
+    }
+
+    /// The levels to take out so the report becomes safe, removing as few as
+    /// possible and no more than `max_removals`. `Some(vec![])` if it is safe
+    /// already and `None` if it can't be made safe.
+    pub fn dampen(&self, levels: &[i32], max_removals: usize) -> Option<Vec<usize>> {
+        if self.is_safe(levels) {
+            return Some(Vec::new());
+        }
+
+        match max_removals {
+            0 => None,
+            1 => self.remove_one(levels).map(|index| vec![index]),
+            _ => [1, -1]
+                .into_iter()
+                .filter_map(|direction| self.fewest_removals(levels, direction))
+                .min_by_key(Vec::len)
+                .filter(|removed| removed.len() <= max_removals),
+        }
+    }
+
+    /// The lowest index whose removal makes the report safe, in linear time.
+    ///
+    /// `prefix[i]` says whether `levels[..=i]` is fine and `suffix[i]` whether `levels[i..]` is,
+    /// so dropping `i` works if both sides of it are and its neighbours can be joined up.
+    fn remove_one(&self, levels: &[i32]) -> Option<usize> {
+        let n = levels.len();
+
+        [1, -1]
+            .into_iter()
+            .filter_map(|direction| {
+                let ok = |from: usize, to: usize| self.step_ok(direction, levels[from], levels[to]);
+
+                let mut prefix = vec![true; n];
+                for i in 1..n {
+                    prefix[i] = prefix[i - 1] && ok(i - 1, i);
+                }
+                let mut suffix = vec![true; n];
+                for i in (0..n.saturating_sub(1)).rev() {
+                    suffix[i] = suffix[i + 1] && ok(i, i + 1);
+                }
+
+                (0..n).find(|&i| {
+                    let before = i == 0 || prefix[i - 1];
+                    let after = i + 1 >= n || suffix[i + 1];
+                    let joined = i == 0 || i + 1 >= n || ok(i - 1, i + 1);
+
+                    before && after && joined
+                })
+            })
+            .min()
+    }
+
+    /// Longest run of levels that can be kept going in `direction`, found by keeping
+    /// track of the fewest removals needed to end on each level. Quadratic, so only
+    /// used when more than one level may go.
+    fn fewest_removals(&self, levels: &[i32], direction: i32) -> Option<Vec<usize>> {
+        let n = levels.len();
+        if n == 0 {
+            return None;
+        }
+
+        // (removals so far, previous kept level) when keeping level i
+        let mut best: Vec<(usize, Option<usize>)> = Vec::with_capacity(n);
+        for i in 0..n {
+            let keep = (0..i)
+                .filter(|&j| self.step_ok(direction, levels[j], levels[i]))
+                .map(|j| (best[j].0 + i - j - 1, Some(j)))
+                .min_by_key(|&(removals, _)| removals);
+
+            best.push(keep.map_or((i, None), |keep| keep.min((i, None))));
+        }
+
+        let last = (0..n).min_by_key(|&i| best[i].0 + n - 1 - i)?;
+
+        let mut kept = vec![false; n];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            kept[i] = true;
+            current = best[i].1;
+        }
+
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_core::testing::Numbers;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[7, 6, 4, 2, 1], Some(vec![]))]
+    #[case(&[1, 2, 7, 8, 9], None)]
+    #[case(&[9, 7, 6, 2, 1], None)]
+    #[case(&[1, 3, 2, 4, 5], Some(vec![1]))]
+    #[case(&[8, 6, 4, 4, 1], Some(vec![2]))]
+    #[case(&[1, 3, 6, 7, 9], Some(vec![]))]
+    #[case(&[6, 2, 3, 4], Some(vec![0]))]
+    #[case(&[1, 2, 6], Some(vec![2]))]
+    fn test_remove_one(#[case] levels: &[i32], #[case] expected: Option<Vec<usize>>) {
+        assert_eq!(expected, Tolerance::default().dampen(levels, 1));
+    }
+
+    #[rstest]
+    #[case(&[1, 9, 2, 9, 3], 2, Some(vec![1, 3]))]
+    #[case(&[1, 9, 2, 9, 3], 1, None)]
+    #[case(&[5, 1, 2, 3, 0], 2, Some(vec![0, 4]))]
+    #[case(&[1, 2, 1, 2, 1, 2], 3, None)]
+    #[case(&[3, 3, 3, 3], 2, None)]
+    #[case(&[3, 3, 3, 3], 3, Some(vec![1, 2, 3]))]
+    fn test_remove_several(
+        #[case] levels: &[i32],
+        #[case] max_removals: usize,
+        #[case] expected: Option<Vec<usize>>,
+    ) {
+        let removed = Tolerance::default().dampen(levels, max_removals);
+
+        assert_eq!(expected, removed);
+        if let Some(removed) = removed {
+            let kept = levels
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !removed.contains(i))
+                .map(|(_, &level)| level)
+                .collect::<Vec<_>>();
+            assert!(Tolerance::default().is_safe(&kept));
+        }
+    }
+
+    #[test]
+    fn test_linear_matches_brute_force() {
+        let tolerance = Tolerance::default();
+        let mut numbers = Numbers::new(12345);
+
+        for _ in 0..2000 {
+            let levels = (0..6)
+                .scan(10, |level, _| {
+                    *level += numbers.between(-4, 4) as i32;
+                    Some(*level)
+                })
+                .collect::<Vec<_>>();
+
+            let brute = (0..levels.len()).find(|&skip| {
+                let kept = levels
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != skip)
+                    .map(|(_, &level)| level)
+                    .collect::<Vec<_>>();
+                tolerance.is_safe(&kept)
+            });
+
+            if tolerance.is_safe(&levels) {
+                assert_eq!(Some(vec![]), tolerance.dampen(&levels, 1));
+            } else {
+                assert_eq!(
+                    brute.map(|skip| vec![skip]),
+                    tolerance.dampen(&levels, 1),
+                    "{levels:?}"
+                );
+                assert_eq!(
+                    brute.is_some(),
+                    tolerance
+                        .dampen(&levels, 2)
+                        .is_some_and(|removed| removed.len() == 1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_tolerance() {
+        let gentle = Tolerance {
+            min_step: 0,
+            max_step: 1,
+        };
+
+        assert!(gentle.is_safe(&[1, 1, 2, 3, 3]));
+        assert!(!gentle.is_safe(&[1, 3]));
+        assert_eq!(Some(vec![4]), gentle.dampen(&[1, 1, 2, 3, 5], 1));
+    }
+
+    #[test]
+    fn test_short_reports() {
+        assert!(Tolerance::default().is_safe(&[]));
+        assert!(Tolerance::default().is_safe(&[4]));
+        assert_eq!(Some(vec![0]), Tolerance::default().dampen(&[4, 4], 1));
+    }
+
+    #[test]
+    fn test_invalid_level() {
+        let Err(AocError::ParseError { span, .. }) = parse_reports("1 2\n3 x") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((6, 1), (span.offset(), span.len()));
+    }
+}
@@ -1,44 +1,169 @@
 use bevy::prelude::*;
+use day_03::scan::{Highlight, Scan};
 
+/// Bytes of memory shown behind and ahead of the cursor
+const BEHIND: usize = 1600;
+const AHEAD: usize = 400;
+
+const FONT_SIZE: f32 = 14.0;
+
+/// The text the cursor moves through, drawn as one span per run of equally highlighted bytes
 #[derive(Component, Debug)]
-struct TextChar;
+struct TextVisualizer;
 
 #[derive(Component, Debug)]
-struct TextVisualizer {
-    content: String,
-    chars: Vec<Entity>,  // References to individual TextChar entities
-    current_position: usize,
-}
+struct TotalText;
 
-// // Resource to hold the original text
-// #[derive(Resource)]
-// struct VisualizerText(String);
+#[derive(Resource, Debug)]
+struct Scanner {
+    scan: Scan<'static>,
+    bytes_per_second: f32,
+    /// the part of a byte left over from earlier frames
+    carry: f32,
+    paused: bool,
+}
 
 fn main() {
     App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "day 3: mull it over".into(),
+                ..default()
+            }),
+            ..default()
+        }))
+        .insert_resource(ClearColor(Color::srgb(0.05, 0.05, 0.08)))
+        .insert_resource(Scanner {
+            scan: Scan::new(include_str!("../../input2.txt")),
+            bytes_per_second: 400.0,
+            carry: 0.0,
+            paused: false,
+        })
         .add_systems(Startup, setup)
-        .add_systems(Update, hello_world).run();
+        .add_systems(Update, (controls, advance, draw_memory, draw_total).chain())
+        .run();
+}
+
+fn colour(highlight: Highlight) -> Color {
+    match highlight {
+        Highlight::Unscanned => Color::srgb(0.35, 0.35, 0.4),
+        Highlight::Scanned => Color::srgb(0.8, 0.8, 0.85),
+        Highlight::Accepted => Color::srgb(0.3, 0.9, 0.4),
+        Highlight::Disabled => Color::srgb(0.9, 0.35, 0.3),
+    }
 }
 
 fn setup(mut commands: Commands) {
-    commands.spawn(Camera2dBundle::default());
-
-    let input_text = "your long text string here".to_string();
-
-    // Spawn the visualizer parent
-    let visualizer = commands
-        .spawn((
-            TextVisualizer {
-                content: input_text.clone(),
-                chars: Vec::new(),
-                current_position: 0,
-            },
-            // Spatial bundle for positioning the whole text block
-            SpatialBundle::default(),
-        ))
-        .id();
-}
-
-fn hello_world() {
-    println!("hello world!");
-}
\ No newline at end of file
+    commands.spawn(Camera2d);
+
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        TotalText,
+    ));
+
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: FONT_SIZE,
+            ..default()
+        },
+        TextLayout::new_with_linebreak(LineBreak::AnyCharacter),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(56.0),
+            left: Val::Px(12.0),
+            right: Val::Px(12.0),
+            ..default()
+        },
+        TextVisualizer,
+    ));
+}
+
+/// Space pauses, up and down change the speed and R starts over
+fn controls(keys: Res<ButtonInput<KeyCode>>, mut scanner: ResMut<Scanner>) {
+    if keys.just_pressed(KeyCode::Space) {
+        scanner.paused = !scanner.paused;
+    }
+    if keys.just_pressed(KeyCode::ArrowUp) {
+        scanner.bytes_per_second *= 2.0;
+    }
+    if keys.just_pressed(KeyCode::ArrowDown) {
+        scanner.bytes_per_second = (scanner.bytes_per_second / 2.0).max(1.0);
+    }
+    if keys.just_pressed(KeyCode::KeyR) {
+        let memory = scanner.scan.memory();
+        scanner.scan = Scan::new(memory);
+        scanner.carry = 0.0;
+    }
+}
+
+fn advance(time: Res<Time>, mut scanner: ResMut<Scanner>) {
+    if scanner.paused || scanner.scan.is_finished() {
+        return;
+    }
+
+    let budget = scanner.carry + scanner.bytes_per_second * time.delta_secs();
+    scanner.carry = budget.fract();
+
+    for step in scanner.scan.advance(budget as usize) {
+        info!("{step}");
+    }
+}
+
+fn draw_memory(mut commands: Commands, scanner: Res<Scanner>, visualizer: Single<Entity, With<TextVisualizer>>) {
+    if !scanner.is_changed() {
+        return;
+    }
+
+    let scan = &scanner.scan;
+    let memory = scan.memory();
+    let cursor = scan.cursor();
+    let end = (cursor + AHEAD).min(memory.len());
+    // the character under the cursor stands out whatever it is
+    let under_cursor = memory.get(cursor..).and_then(|rest| rest.chars().next()).map(|c| cursor..cursor + c.len_utf8());
+
+    let mut pieces = scan.runs(cursor.saturating_sub(BEHIND)..cursor);
+    if let Some(range) = &under_cursor {
+        pieces.push((range.clone(), Highlight::Unscanned));
+    }
+    pieces.extend(scan.runs(under_cursor.map_or(cursor, |range| range.end)..end));
+
+    commands.entity(*visualizer).despawn_descendants().with_children(|parent| {
+        for (range, highlight) in pieces {
+            let colour = if range.start == cursor { Color::srgb(1.0, 0.85, 0.2) } else { colour(highlight) };
+
+            parent.spawn((
+                TextSpan::new(&memory[range]),
+                TextFont {
+                    font_size: FONT_SIZE,
+                    ..default()
+                },
+                TextColor(colour),
+            ));
+        }
+    });
+}
+
+fn draw_total(scanner: Res<Scanner>, mut total: Single<&mut Text, With<TotalText>>) {
+    let scan = &scanner.scan;
+
+    total.0 = format!(
+        "total: {}   {} / {} bytes   mul {}   {} bytes/s{}",
+        scan.total(),
+        scan.cursor(),
+        scan.memory().len(),
+        if scan.enabled() { "enabled" } else { "disabled" },
+        scanner.bytes_per_second,
+        if scanner.paused { "   paused" } else { "" },
+    );
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+use crate::tokenizer::{Instruction, Token};
+
+/// What running an instruction did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// a `mul` whose product went into the total
+    Accepted(u64),
+    /// a `mul` while instructions are disabled
+    Skipped,
+    Enabled,
+    Disabled,
+}
+
+/// One line of the trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step<'a> {
+    /// byte offset into the memory
+    pub offset: usize,
+    pub text: &'a str,
+    pub instruction: Instruction,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Step<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>6}  {:<16}", self.offset, self.text)?;
+        match self.outcome {
+            Outcome::Accepted(product) => write!(f, "+{product}"),
+            Outcome::Skipped => write!(f, "skipped"),
+            Outcome::Enabled => write!(f, "enabled"),
+            Outcome::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<'a> {
+    pub total: u64,
+    pub steps: Vec<Step<'a>>,
+}
+
+impl fmt::Display for Trace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{step}")?;
+        }
+        write!(f, "total {}", self.total)
+    }
+}
+
+/// Runs instructions one at a time, so the state can be looked at in between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interpreter {
+    /// whether `do()` and `don't()` switch `mul` on and off, part 1 ignores them
+    pub conditionals: bool,
+    pub enabled: bool,
+    pub total: u64,
+}
+
+impl Interpreter {
+    pub fn new(conditionals: bool) -> Self {
+        Self { conditionals, enabled: true, total: 0 }
+    }
+
+    /// `None` for garbage
+    pub fn execute<'a>(&mut self, token: &Token<'a>) -> Option<Step<'a>> {
+        let instruction = token.instruction()?;
+
+        let outcome = match instruction {
+            Instruction::Mul(a, b) if self.enabled || !self.conditionals => {
+                let product = a as u64 * b as u64;
+                self.total += product;
+                Outcome::Accepted(product)
+            }
+            Instruction::Mul(..) => Outcome::Skipped,
+            Instruction::Do => {
+                self.enabled = true;
+                Outcome::Enabled
+            }
+            Instruction::Dont => {
+                self.enabled = false;
+                Outcome::Disabled
+            }
+        };
+
+        Some(Step { offset: token.offset(), text: token.text(), instruction, outcome })
+    }
+
+    pub fn run<'a>(mut self, tokens: impl IntoIterator<Item = Token<'a>>) -> Trace<'a> {
+        let steps = tokens.into_iter().filter_map(|token| self.execute(&token)).collect();
+
+        Trace { total: self.total, steps }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tokenizer::tokenize;
+
+    const EXAMPLE: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_trace() {
+        let trace = Interpreter::new(true).run(tokenize(EXAMPLE));
+
+        assert_eq!(48, trace.total);
+        assert_eq!(
+            "     1  mul(2,4)        +8
+    20  don't()         disabled
+    28  mul(5,5)        skipped
+    48  mul(11,8)       skipped
+    59  do()            enabled
+    64  mul(8,5)        +40
+total 48",
+            trace.to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_conditionals() {
+        let trace = Interpreter::new(false).run(tokenize(EXAMPLE));
+
+        assert_eq!(161, trace.total);
+        assert!(!trace.steps.iter().any(|step| step.outcome == Outcome::Skipped));
+    }
+
+    #[test]
+    fn test_step_by_step() {
+        let mut interpreter = Interpreter::new(true);
+        let mut tokens = tokenize("don't()?mul(2,3)");
+
+        assert_eq!(Outcome::Disabled, interpreter.execute(&tokens.next().unwrap()).unwrap().outcome);
+        assert!(!interpreter.enabled);
+        assert_eq!(None, interpreter.execute(&tokens.next().unwrap()));
+        assert_eq!(Outcome::Skipped, interpreter.execute(&tokens.next().unwrap()).unwrap().outcome);
+        assert_eq!(0, interpreter.total);
+    }
+}
@@ -1,54 +1,10 @@
-use nom::error::{Error, ErrorKind};
-// use tracing::{debug, instrument};
-
-use crate::{custom_error::AocError, parse_mul, Product};
+use crate::{custom_error::AocError, interpreter::Interpreter, tokenizer::tokenize};
 
 #[tracing::instrument(skip(input))]
-pub fn process(mut input: &str) -> miette::Result<String, AocError> {
-    let mut sum = Vec::new();
-
-    while !input.is_empty() {
-        match parse_mul(input) {
-            Ok((remainder, (a, b))) => {
-                sum.push(Product::new(a, b));
-
-                input = remainder;
-                // debug!("remainder");
-            }
-            Err(e) => {
-                dbg!(&e);
-
-                if let nom::Err::Error(err) = &e {
-                    match err {
-                        // invalid terminator but string is not empty
-                        Error {
-                            input: _,
-                            code: ErrorKind::Char,
-                        } => {
-                            if input.len() > 1 {
-                                input = &input[1..];
-                            } else {
-                                panic!("{e:?}");
-                            }
-                        }
-                        Error {
-                            input: _,
-                            code: ErrorKind::TakeUntil,
-                        } => {
-                            input = "";
-                        }
-                        _ => panic!("{e:?}"),
-                    }
-                } else {
-                    panic!("{e:?}");
-                }
-            }
-        }
-    }
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    let trace = Interpreter::new(false).run(tokenize(input));
 
-    dbg!(&sum);
-    // dbg!(&sum.len());
-    Ok(sum.iter().map(|p| p.value()).sum::<usize>().to_string())
+    Ok(trace.total.to_string())
 }
 
 #[cfg(test)]
@@ -1,79 +1,13 @@
-use nom::{error::{Error, ErrorKind}, IResult};
-use crate::{custom_error::AocError, parse_mul, Product};
+use crate::{custom_error::AocError, interpreter::Interpreter, tokenizer::tokenize};
 
+#[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let first_do = input.find("do()");
-    let first_dont = input.find("don't()");
+    let trace = Interpreter::new(true).run(tokenize(input));
 
-    if let (_, None) = (first_do, first_dont) {
-        return crate::part1::process(input);
-    }
-
-    // Process everything before the first don't()
-    let (first, mut remainder) = input.split_at(first_dont.unwrap());
-    let mut total = crate::part1::process(first).unwrap().parse::<usize>().unwrap();
-    
-    while !remainder.is_empty() {
-        if let Some(dont_idx) = remainder.find("don't()") {
-            // Find next do() after this don't()
-            if let Some(do_idx) = remainder[dont_idx..].find("do()") {
-                // Skip everything between don't() and do()
-                let next_dont = remainder[dont_idx + do_idx..].find("don't()");
-                let end_idx = match next_dont {
-                    Some(idx) => dont_idx + do_idx + idx,
-                    None => remainder.len()
-                };
-                
-                // Process the chunk between do() and next don't() (or end)
-                let process_chunk = &remainder[dont_idx + do_idx..end_idx];
-                let sum: Vec<Product> = Vec::new();
-                if let Ok((_, products)) = parse(sum, process_chunk) {
-                    total += products.iter().map(|p| p.value()).sum::<usize>();
-                }
-                
-                remainder = &remainder[end_idx..];
-            } else {
-                break;
-            }
-        } else {
-            break;
-        }
-    }
-
-    Ok(total.to_string())
+    tracing::debug!("\n{trace}");
+    Ok(trace.total.to_string())
 }
 
-fn parse<'a>(mut sum: Vec<Product>, input: &'a str) -> IResult<&'a str, Vec<Product>> {
-    let mut current_section = input;
-    
-    loop {
-        match parse_mul(current_section) {
-            Ok((remaining_section, (a, b))) => {
-                sum.push(Product::new(a, b));
-                current_section = remaining_section;
-            },
-            Err(e) => {
-                if let nom::Err::Error(err) = &e {
-                    match err {
-                        Error { code: ErrorKind::Char, .. } => {
-                            if current_section.len() > 1 {
-                                current_section = &current_section[1..];
-                                continue;
-                            }
-                            break;
-                        },
-                        Error { code: ErrorKind::TakeUntil, .. } => break,
-                        _ => break,
-                    }
-                } else {
-                    break;
-                }
-            }
-        }
-    }
-    
-    Ok((current_section, sum))
-}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,13 +56,8 @@ mod tests {
 //     // #[case("don't()mul(852,168)&where()&/!*{mul(644,741)>;-&where()}{who()@mul(598,266)]!>{*-mul(360,534)- mul(622,4)^,mul(364,523);-where()',}]mul(392,56)(why(786,236){&+mul(421,577)'from()select()*+who(635,837)~mul(813,646)$>!mul(849,457)select()who()?-+mul(345,668)who();mul(456,636)from()mul(524,899)}(! (how()mul(599,822)select(824,479)mul(996,930)}what(756,659))^*%mul(641,495)(#]what()*,'*%(mul(743,569)$[*select()when()~>(+mul(759,831):<from(35,464)what()&from()^%]#mul(268,525)(/-where(691,810){:+&}when()mul(757,376)when() #,)#how()$ mul(238,904)]/[select()#what()?~mul(524,81)select()~where()how()", 0)]
     // #[case("", 0)]
     #[case("don't()#mul? ~mul(990,88)$/mul(986,722)?;from()where(394,512)how(593,980)&+mul(667,464);mul(390,181)]who()what()*}+#;mul(429,936)'<:-'+^}mul(346,607-/where()select()%><who()mul(914,888)mul(781,920):*mul(954,791))({who()?~$)mul(769,183)#['{^)*-mul(330,184)select()%what()'who()where()when()(mul(988,148)&who()*&/[/'mul(327,74)/+;?/select()mul(315,381)don't()>}/-#~why()!*mul(721,722),>why() mul(583,596)when()%}$mul(482,164)$mul(230,264)mul(752,60)@'mul(47,57)(mul(17,292)select()where()%)>@why()mul(302,101)<<how()!-[!from()]mul(649,528)^]@^$mul(577,114)/<mul(579,480)who(808,216), #when()why()?mul(979,878)$+why(712,413)$mul:-from()%&}select()mul(539,991)$?>?when()how(761,642)from()mul(501,428)}#how()'what()/+{<+mul(793,630){[$;:[!:what()%don't()what()>from(825,940)' </^&mul(212,563)~when()mul(943,607)where(802,717)'[when()<who())&how()mul(696,659)mul(156//,(mul(477,156)*when(952,865)#*?!>%>!", 0)]
-    fn test_parse(#[case] input: &str, #[case] expected: usize) {
-        let sum: Vec<Product> = Vec::new();
-        let (_remainder, v) = parse(sum, input).unwrap();
-
-        assert_eq!(v.iter().map(|p| p.value()).sum::<usize>(), expected);
-        // assert_eq!(expected.to_string(), process(input)?);
-        // Ok(())
+    fn test_disabled(#[case] input: &str, #[case] expected: usize) {
+        assert_eq!(expected.to_string(), process(input).unwrap());
     }
 
     #[test]
@@ -0,0 +1,182 @@
+use std::ops::Range;
+
+use crate::interpreter::{Interpreter, Outcome, Step};
+use crate::tokenizer::{Token, tokenize};
+
+/// How a byte of memory is shown once the cursor has passed it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// the cursor hasn't got here yet
+    Unscanned,
+    Scanned,
+    /// part of a `mul` that counted towards the total
+    Accepted,
+    /// read while instructions were switched off
+    Disabled,
+}
+
+/// A cursor moving through memory, running each instruction as soon as it has
+/// read all of it. Knows nothing about drawing, the visualizer just asks it what
+/// colour each byte is.
+#[derive(Debug, Clone)]
+pub struct Scan<'a> {
+    memory: &'a str,
+    tokens: Vec<Token<'a>>,
+    /// the first token that hasn't been run yet
+    next_token: usize,
+    cursor: usize,
+    interpreter: Interpreter,
+    highlights: Vec<Highlight>,
+}
+
+impl<'a> Scan<'a> {
+    pub fn new(memory: &'a str) -> Self {
+        Self {
+            memory,
+            tokens: tokenize(memory).collect(),
+            next_token: 0,
+            cursor: 0,
+            interpreter: Interpreter::new(true),
+            highlights: vec![Highlight::Unscanned; memory.len()],
+        }
+    }
+
+    pub fn memory(&self) -> &'a str {
+        self.memory
+    }
+
+    /// Byte offset of the next byte to be read
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn total(&self) -> u64 {
+        self.interpreter.total
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.interpreter.enabled
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.memory.len()
+    }
+
+    pub fn highlights(&self) -> &[Highlight] {
+        &self.highlights
+    }
+
+    /// Moves the cursor up to `bytes` further on, returning the instructions
+    /// that were completed on the way
+    pub fn advance(&mut self, bytes: usize) -> Vec<Step<'a>> {
+        let target = self.cursor.saturating_add(bytes).min(self.memory.len());
+        let mut steps = Vec::new();
+
+        while self.cursor < target {
+            self.highlights[self.cursor] = if self.interpreter.enabled { Highlight::Scanned } else { Highlight::Disabled };
+            self.cursor += 1;
+
+            while let Some(token) = self.tokens.get(self.next_token) {
+                let end = token.offset() + token.text().len();
+                if end > self.cursor {
+                    break;
+                }
+
+                if let Some(step) = self.interpreter.execute(token) {
+                    if let Outcome::Accepted(_) = step.outcome {
+                        self.highlights[token.offset()..end].fill(Highlight::Accepted);
+                    }
+                    steps.push(step);
+                }
+                self.next_token += 1;
+            }
+        }
+
+        steps
+    }
+
+    /// Consecutive bytes in `range` that look the same, so they can be drawn as one
+    /// piece of text. Ranges are widened to whole characters.
+    pub fn runs(&self, range: Range<usize>) -> Vec<(Range<usize>, Highlight)> {
+        let mut runs: Vec<(Range<usize>, Highlight)> = Vec::new();
+
+        for (offset, c) in self.memory.char_indices().filter(|(offset, _)| range.contains(offset)) {
+            let highlight = self.highlights[offset];
+            let end = offset + c.len_utf8();
+
+            match runs.last_mut() {
+                Some((run, last)) if *last == highlight => run.end = end,
+                _ => runs.push((offset..end, highlight)),
+            }
+        }
+
+        runs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Highlight::*;
+
+    const EXAMPLE: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_runs_to_the_end() {
+        let mut scan = Scan::new(EXAMPLE);
+
+        let steps = scan.advance(usize::MAX);
+        assert_eq!(6, steps.len());
+        assert_eq!(48, scan.total());
+        assert!(scan.is_finished());
+        assert!(scan.advance(10).is_empty());
+    }
+
+    #[test]
+    fn test_instruction_runs_once_fully_read() {
+        let mut scan = Scan::new(EXAMPLE);
+
+        // one byte short of the end of mul(2,4)
+        assert!(scan.advance(8).is_empty());
+        assert_eq!(0, scan.total());
+        assert_eq!(vec![(0..8, Scanned), (8..EXAMPLE.len(), Unscanned)], scan.runs(0..EXAMPLE.len()));
+
+        let steps = scan.advance(1);
+        assert_eq!(1, steps.len());
+        assert_eq!(8, scan.total());
+        assert_eq!(vec![(0..1, Scanned), (1..9, Accepted)], scan.runs(0..9));
+    }
+
+    #[test]
+    fn test_disabled_region() {
+        let mut scan = Scan::new(EXAMPLE);
+        scan.advance(64);
+
+        assert!(scan.enabled());
+        assert_eq!(
+            vec![
+                (0..1, Scanned),
+                (1..9, Accepted),
+                // don't() and do() take effect once they have been read
+                (9..27, Scanned),
+                (27..63, Disabled),
+                (63..64, Scanned),
+            ],
+            scan.runs(0..64)
+        );
+
+        scan.advance(8);
+        assert_eq!(vec![(64..72, Accepted)], scan.runs(64..72));
+        assert_eq!(48, scan.total());
+    }
+
+    #[test]
+    fn test_runs_whole_characters() {
+        let mut scan = Scan::new("é?mul(1,1)");
+        scan.advance(1);
+
+        assert_eq!(vec![(0..2, Scanned)], scan.runs(0..2));
+        assert_eq!(vec![(2..3, Unscanned)], scan.runs(2..3));
+    }
+}
@@ -0,0 +1,169 @@
+use nom::{
+    IResult,
+    branch::alt,
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{anychar, char},
+    combinator::{consumed, map, map_res, not, recognize, value},
+    multi::many1_count,
+    sequence::{delimited, preceded, separated_pair},
+};
+use nom_locate::LocatedSpan;
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Instruction(Instruction),
+    /// a run of corrupted memory between instructions
+    Garbage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub span: Span<'a>,
+}
+
+impl<'a> Token<'a> {
+    /// Byte offset into the memory
+    pub fn offset(&self) -> usize {
+        self.span.location_offset()
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.span.fragment()
+    }
+
+    pub fn instruction(&self) -> Option<Instruction> {
+        match self.kind {
+            TokenKind::Instruction(instruction) => Some(instruction),
+            TokenKind::Garbage => None,
+        }
+    }
+}
+
+/// 1 to 3 digits, anything longer isn't a valid operand
+fn operand(input: Span) -> IResult<Span, u32> {
+    map_res(take_while_m_n(1, 3, |c: char| c.is_ascii_digit()), |digits: Span| {
+        digits.fragment().parse::<u32>()
+    })(input)
+}
+
+fn instruction(input: Span) -> IResult<Span, Instruction> {
+    alt((
+        map(
+            delimited(tag("mul("), separated_pair(operand, char(','), operand), char(')')),
+            |(a, b)| Instruction::Mul(a, b),
+        ),
+        value(Instruction::Do, tag("do()")),
+        value(Instruction::Dont, tag("don't()")),
+    ))(input)
+}
+
+/// Everything up to the next valid instruction
+fn garbage(input: Span) -> IResult<Span, Span> {
+    recognize(many1_count(preceded(not(instruction), anychar)))(input)
+}
+
+fn token(input: Span) -> IResult<Span, Token> {
+    alt((
+        map(consumed(instruction), |(span, instruction)| Token {
+            kind: TokenKind::Instruction(instruction),
+            span,
+        }),
+        map(garbage, |span| Token { kind: TokenKind::Garbage, span }),
+    ))(input)
+}
+
+/// Splits memory into instructions and the garbage between them, in one pass.
+/// Every byte ends up in exactly one token.
+pub struct Tokens<'a> {
+    rest: Span<'a>,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rest, token) = token(self.rest).ok()?;
+        self.rest = rest;
+        Some(token)
+    }
+}
+
+pub fn tokenize(memory: &str) -> Tokens<'_> {
+    Tokens { rest: Span::new(memory) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn kinds(memory: &str) -> Vec<(usize, &str, TokenKind)> {
+        tokenize(memory).map(|token| (token.offset(), token.text(), token.kind)).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            vec![
+                (0, "x", TokenKind::Garbage),
+                (1, "mul(2,4)", TokenKind::Instruction(Instruction::Mul(2, 4))),
+                (9, "&mul[3,7]!^", TokenKind::Garbage),
+                (20, "don't()", TokenKind::Instruction(Instruction::Dont)),
+                (27, "_", TokenKind::Garbage),
+                (28, "mul(5,5)", TokenKind::Instruction(Instruction::Mul(5, 5))),
+                (36, "+mul(32,64](", TokenKind::Garbage),
+                (48, "mul(11,8)", TokenKind::Instruction(Instruction::Mul(11, 8))),
+                (57, "un", TokenKind::Garbage),
+                (59, "do()", TokenKind::Instruction(Instruction::Do)),
+                (63, "?", TokenKind::Garbage),
+                (64, "mul(8,5)", TokenKind::Instruction(Instruction::Mul(8, 5))),
+                (72, ")", TokenKind::Garbage),
+            ],
+            kinds("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))")
+        );
+    }
+
+    #[rstest]
+    #[case("mul(1234,5)")]
+    #[case("mul(12,)")]
+    #[case("mul(,5)")]
+    #[case("mul( 1,5)")]
+    #[case("mul(1,5")]
+    #[case("do(")]
+    #[case("dont()")]
+    fn test_not_instructions(#[case] memory: &str) {
+        assert_eq!(vec![(0, memory, TokenKind::Garbage)], kinds(memory));
+    }
+
+    #[test]
+    fn test_operand_bounds() {
+        assert_eq!(
+            vec![(0, "mul(999,0)", TokenKind::Instruction(Instruction::Mul(999, 0)))],
+            kinds("mul(999,0)")
+        );
+        // a valid instruction can start inside what looked like a broken one
+        assert_eq!(
+            vec![(0, "mul(", TokenKind::Garbage), (4, "mul(1,2)", TokenKind::Instruction(Instruction::Mul(1, 2)))],
+            kinds("mul(mul(1,2)")
+        );
+    }
+
+    #[test]
+    fn test_covers_everything() {
+        let memory = "where()mul(1,2)'mul(3,4]don't()do()é";
+
+        assert_eq!(memory, tokenize(memory).map(|token| token.text()).collect::<String>());
+        assert!(tokenize("").next().is_none());
+    }
+}
@@ -1,9 +1,32 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::invalid_words))]
+    InvalidWords(#[from] aho_corasick::BuildError),
+
+    #[error("word {0} is empty")]
+    #[diagnostic(code(aoc::empty_word))]
+    EmptyWord(usize),
+
+    #[error("shape has no letters to match:\n{0}")]
+    #[diagnostic(code(aoc::empty_shape), help("`.` matches anything, so a shape needs at least one other character"))]
+    EmptyShape(String),
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::InvalidWords(..) | Self::EmptyWord(..) | Self::EmptyShape(..) => ErrorKind::InvalidInput,
+        }
+    }
+}
+
@@ -0,0 +1,300 @@
+use std::collections::BTreeSet;
+
+use aho_corasick::AhoCorasick;
+use glam::IVec2;
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    North,
+    NorthEast,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+        Direction::North,
+        Direction::NorthEast,
+    ];
+
+    /// One step in this direction, with y growing downwards
+    pub fn delta(self) -> IVec2 {
+        match self {
+            Direction::East => IVec2::new(1, 0),
+            Direction::SouthEast => IVec2::new(1, 1),
+            Direction::South => IVec2::new(0, 1),
+            Direction::SouthWest => IVec2::new(-1, 1),
+            Direction::West => IVec2::new(-1, 0),
+            Direction::NorthWest => IVec2::new(-1, -1),
+            Direction::North => IVec2::new(0, -1),
+            Direction::NorthEast => IVec2::new(1, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    /// index into the words that were searched for
+    pub word: usize,
+    /// the first letter
+    pub start: IVec2,
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeMatch {
+    /// top left corner of the shape's bounding box
+    pub origin: IVec2,
+    /// index into `Shape::variants`
+    pub variant: usize,
+}
+
+/// A small 2-D pattern, written as rows of text where `.` matches anything
+/// and every other character has to match exactly.
+///
+/// ```text
+/// M.S
+/// .A.
+/// M.S
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    variants: Vec<Vec<(IVec2, u8)>>,
+}
+
+impl Shape {
+    pub fn parse(pattern: &str) -> miette::Result<Self, AocError> {
+        let cells = pattern
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.bytes()
+                    .enumerate()
+                    .filter(|&(_, c)| c != b'.')
+                    .map(move |(x, c)| (IVec2::new(x as i32, y as i32), c))
+            })
+            .collect::<Vec<_>>();
+
+        if cells.is_empty() {
+            return Err(AocError::EmptyShape(pattern.to_string()));
+        }
+
+        // four rotations, each with and without a mirror, with duplicates dropped
+        let mut variants = Vec::new();
+        let mut seen = BTreeSet::new();
+        for mirror in [false, true] {
+            let mut rotated = cells.clone();
+            for _ in 0..4 {
+                let variant = normalise(
+                    rotated
+                        .iter()
+                        .map(|&(pos, c)| (if mirror { IVec2::new(-pos.x, pos.y) } else { pos }, c))
+                        .collect(),
+                );
+                if seen.insert(variant.iter().map(|&(pos, c)| (pos.y, pos.x, c)).collect::<Vec<_>>()) {
+                    variants.push(variant);
+                }
+                rotated = rotated.into_iter().map(|(pos, c)| (pos.perp(), c)).collect();
+            }
+        }
+
+        Ok(Self { variants })
+    }
+
+    /// Every distinct way the shape can be rotated or reflected, as offsets from the
+    /// top left of its bounding box
+    pub fn variants(&self) -> &[Vec<(IVec2, u8)>] {
+        &self.variants
+    }
+}
+
+/// Moves the cells so the bounding box starts at the origin, in reading order
+fn normalise(mut cells: Vec<(IVec2, u8)>) -> Vec<(IVec2, u8)> {
+    let min = cells.iter().fold(IVec2::MAX, |min, &(pos, _)| min.min(pos));
+
+    cells.iter_mut().for_each(|(pos, _)| *pos -= min);
+    cells.sort_by_key(|&(pos, _)| (pos.y, pos.x));
+    cells
+}
+
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    width: usize,
+    height: usize,
+    /// row major, short rows padded with 0 so they never match
+    letters: Vec<u8>,
+}
+
+impl WordSearch {
+    pub fn parse(input: &str) -> Self {
+        let rows = input.lines().collect::<Vec<_>>();
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        let mut letters = vec![0; width * rows.len()];
+        for (y, row) in rows.iter().enumerate() {
+            letters[y * width..y * width + row.len()].copy_from_slice(row.as_bytes());
+        }
+
+        Self { width, height: rows.len(), letters }
+    }
+
+    fn get(&self, pos: IVec2) -> Option<u8> {
+        (pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height)
+            .then(|| self.letters[pos.y as usize * self.width + pos.x as usize])
+    }
+
+    /// Every line through the grid in `direction`, with the cell it starts from.
+    /// A line starts wherever stepping backwards leaves the grid, so each cell is on exactly one.
+    fn lines(&self, direction: Direction) -> impl Iterator<Item = (IVec2, Vec<u8>)> + '_ {
+        let delta = direction.delta();
+
+        (0..self.height as i32)
+            .flat_map(move |y| (0..self.width as i32).map(move |x| IVec2::new(x, y)))
+            .filter(move |&pos| self.get(pos - delta).is_none())
+            .map(move |start| {
+                let line = std::iter::successors(Some(start), |&pos| Some(pos + delta))
+                    .map_while(|pos| self.get(pos))
+                    .collect();
+                (start, line)
+            })
+    }
+
+    /// Every occurrence of every word, read in all 8 directions, overlaps included.
+    ///
+    /// Each direction is cut into lines which are all scanned for every word at once.
+    pub fn find_words(&self, words: &[&str]) -> miette::Result<Vec<WordMatch>, AocError> {
+        if let Some(word) = words.iter().position(|word| word.is_empty()) {
+            return Err(AocError::EmptyWord(word));
+        }
+
+        let automaton = AhoCorasick::new(words)?;
+        let mut matches = Vec::new();
+
+        for direction in Direction::ALL {
+            for (start, line) in self.lines(direction) {
+                matches.extend(automaton.find_overlapping_iter(&line).map(|found| WordMatch {
+                    word: found.pattern().as_usize(),
+                    start: start + direction.delta() * found.start() as i32,
+                    direction,
+                }));
+            }
+        }
+
+        matches.sort_by_key(|found| (found.start.y, found.start.x, found.direction, found.word));
+        Ok(matches)
+    }
+
+    /// Every placement of the shape, in any rotation or reflection, in reading order
+    pub fn find_shape(&self, shape: &Shape) -> Vec<ShapeMatch> {
+        (0..self.height as i32)
+            .flat_map(|y| (0..self.width as i32).map(move |x| IVec2::new(x, y)))
+            .flat_map(|origin| {
+                shape
+                    .variants()
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, cells)| cells.iter().all(|&(offset, c)| self.get(origin + offset) == Some(c)))
+                    .map(move |(variant, _)| ShapeMatch { origin, variant })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_small_example() -> miette::Result<()> {
+        let search = WordSearch::parse("..X...\n.SAMX.\n.A..A.\nXMAS.S\n.X....");
+
+        assert_eq!(
+            vec![
+                WordMatch { word: 0, start: IVec2::new(2, 0), direction: Direction::SouthEast },
+                WordMatch { word: 0, start: IVec2::new(4, 1), direction: Direction::West },
+                WordMatch { word: 0, start: IVec2::new(0, 3), direction: Direction::East },
+                WordMatch { word: 0, start: IVec2::new(1, 4), direction: Direction::North },
+            ],
+            search.find_words(&["XMAS"])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_directions() -> miette::Result<()> {
+        let matches = WordSearch::parse(EXAMPLE).find_words(&["XMAS"])?;
+
+        let count = |direction| matches.iter().filter(|found| found.direction == direction).count();
+        assert_eq!(18, matches.len());
+        assert_eq!(
+            [3, 1, 1, 1, 2, 4, 2, 4],
+            Direction::ALL.map(count)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_several_words() -> miette::Result<()> {
+        let matches = WordSearch::parse("CATS\nA..T\nT..O\nSTOP").find_words(&["CAT", "CATS", "STOP", "TO"])?;
+
+        let words = |word| matches.iter().filter(|found| found.word == word).count();
+        // overlapping words are all found, CAT inside CATS too
+        assert_eq!((2, 2, 2, 2), (words(0), words(1), words(2), words(3)));
+        assert!(matches.contains(&WordMatch { word: 2, start: IVec2::new(3, 0), direction: Direction::South }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_x_mas_shape() -> miette::Result<()> {
+        let shape = Shape::parse("M.S\n.A.\nM.S")?;
+
+        // turning the X round gives four different placements of the letters
+        // and mirroring doesn't add any more
+        assert_eq!(4, shape.variants().len());
+        assert_eq!(9, WordSearch::parse(EXAMPLE).find_shape(&shape).len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_asymmetric_shape() -> miette::Result<()> {
+        let shape = Shape::parse("AB\n.C")?;
+        let search = WordSearch::parse("AB.\n.C.\nCBA");
+
+        // the second one is turned half way round
+        assert_eq!(8, shape.variants().len());
+        assert_eq!(
+            vec![ShapeMatch { origin: IVec2::new(0, 0), variant: 0 }, ShapeMatch { origin: IVec2::new(1, 1), variant: 2 }],
+            search.find_shape(&shape)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(matches!(Shape::parse("..\n.."), Err(AocError::EmptyShape(_))));
+        assert!(matches!(WordSearch::parse("AB").find_words(&["A", ""]), Err(AocError::EmptyWord(1))));
+    }
+}
@@ -1,9 +1,30 @@
-use miette::Diagnostic;
+use aoc_core::{Classify, ErrorKind};
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("the rules for update {update} go round in a cycle")]
+    #[diagnostic(code(aoc::rule_cycle), help("no order of these pages can satisfy all of these rules"))]
+    RuleCycle {
+        update: String,
+        #[source_code]
+        src: String,
+        #[label(collection, "part of the cycle")]
+        rules: Vec<SourceSpan>,
+    },
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::RuleCycle { .. } => ErrorKind::NoSolution,
+        }
+    }
+}
+
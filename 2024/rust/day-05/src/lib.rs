@@ -1,5 +1,6 @@
 pub mod custom_error;
 
+pub mod ordering;
 pub mod part1;
 pub mod part2;
 
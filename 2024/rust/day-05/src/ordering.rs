@@ -0,0 +1,278 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use miette::SourceSpan;
+
+use crate::custom_error::AocError;
+
+/// `before` has to be printed at some point before `after`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub before: u32,
+    pub after: u32,
+    /// where the rule sits in the input, for diagnostics
+    pub span: SourceSpan,
+}
+
+/// Which page goes next when the rules allow several
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// the one that came first in the update, so already ordered updates stay as they are
+    #[default]
+    Stable,
+    /// the lowest page number, giving the lexicographically smallest ordering
+    Lexicographic,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrintQueue<'a> {
+    source: &'a str,
+    pub rules: Vec<Rule>,
+    pub updates: Vec<Vec<u32>>,
+}
+
+/// The page in the middle of an update
+pub fn middle(update: &[u32]) -> u32 {
+    update[update.len() / 2]
+}
+
+fn offset(input: &str, line: &str) -> usize {
+    line.as_ptr() as usize - input.as_ptr() as usize
+}
+
+impl<'a> PrintQueue<'a> {
+    pub fn parse(input: &'a str) -> miette::Result<Self, AocError> {
+        let mut rules = Vec::new();
+        let mut updates = Vec::new();
+        let mut lines = input.lines();
+
+        for line in lines.by_ref().take_while(|line| !line.trim().is_empty()) {
+            let invalid = || -> AocError {
+                aoc_core::AocError::parse_at(input, line, "expected a page ordering rule like 47|53").into()
+            };
+
+            let (before, after) = line.trim().split_once('|').ok_or_else(invalid)?;
+            rules.push(Rule {
+                before: before.parse().map_err(|_| invalid())?,
+                after: after.parse().map_err(|_| invalid())?,
+                span: (offset(input, line), line.len()).into(),
+            });
+        }
+
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let invalid = || -> AocError {
+                aoc_core::AocError::parse_at(input, line, "expected comma separated pages, each listed once").into()
+            };
+
+            let update = line
+                .trim()
+                .split(',')
+                .map(|page| page.parse().map_err(|_| invalid()))
+                .collect::<Result<Vec<u32>, _>>()?;
+
+            let mut pages = update.clone();
+            pages.sort_unstable();
+            if pages.windows(2).any(|pair| pair[0] == pair[1]) {
+                return Err(invalid());
+            }
+
+            updates.push(update);
+        }
+
+        Ok(Self { source: input, rules, updates })
+    }
+
+    /// The rules where both pages are in the update, as (rule, before, after)
+    /// with the pages given by their position in the update
+    fn induced(&self, update: &[u32]) -> Vec<(usize, usize, usize)> {
+        let positions: HashMap<u32, usize> = update.iter().enumerate().map(|(i, &page)| (page, i)).collect();
+
+        self.rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| Some((i, *positions.get(&rule.before)?, *positions.get(&rule.after)?)))
+            .collect()
+    }
+
+    pub fn is_ordered(&self, update: &[u32]) -> bool {
+        self.induced(update).into_iter().all(|(_, before, after)| before < after)
+    }
+
+    /// Puts the update in an order that satisfies every rule between its pages.
+    ///
+    /// Kahn's algorithm on just the rules the update is affected by, since the full
+    /// rule set may well be cyclic. If even those can't be satisfied, the error
+    /// points at the rules that form the cycle.
+    pub fn sort(&self, update: &[u32], order: Order) -> miette::Result<Vec<u32>, AocError> {
+        let edges = self.induced(update);
+
+        let mut incoming = vec![0; update.len()];
+        let mut successors = vec![Vec::new(); update.len()];
+        let mut predecessors = vec![Vec::new(); update.len()];
+        for &(rule, before, after) in &edges {
+            incoming[after] += 1;
+            successors[before].push(after);
+            predecessors[after].push((before, rule));
+        }
+
+        let key = |page: usize| match order {
+            Order::Stable => page as u32,
+            Order::Lexicographic => update[page],
+        };
+
+        let mut ready: BinaryHeap<Reverse<(u32, usize)>> = (0..update.len())
+            .filter(|&page| incoming[page] == 0)
+            .map(|page| Reverse((key(page), page)))
+            .collect();
+        let mut sorted = Vec::with_capacity(update.len());
+
+        while let Some(Reverse((_, page))) = ready.pop() {
+            sorted.push(update[page]);
+
+            for &next in &successors[page] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(Reverse((key(next), next)));
+                }
+            }
+        }
+
+        if sorted.len() == update.len() {
+            return Ok(sorted);
+        }
+
+        Err(self.cycle(update, &incoming, &predecessors))
+    }
+
+    /// Every page left over still waits on another left over page, so walking
+    /// backwards through those has to come round in a loop eventually
+    fn cycle(&self, update: &[u32], incoming: &[usize], predecessors: &[Vec<(usize, usize)>]) -> AocError {
+        let mut page = (0..update.len()).find(|&page| incoming[page] > 0).expect("some pages are left over");
+        let mut walked: Vec<(usize, usize)> = Vec::new();
+
+        let start = loop {
+            if let Some(start) = walked.iter().position(|&(from, _)| from == page) {
+                break start;
+            }
+
+            let &(before, rule) = predecessors[page]
+                .iter()
+                .find(|&&(before, _)| incoming[before] > 0)
+                .expect("left over pages wait on each other");
+            walked.push((page, rule));
+            page = before;
+        };
+
+        let mut rules: Vec<SourceSpan> = walked[start..].iter().map(|&(_, rule)| self.rules[rule].span).collect();
+        rules.sort_by_key(|span| span.offset());
+
+        AocError::RuleCycle {
+            update: update.iter().map(u32::to_string).collect::<Vec<_>>().join(","),
+            src: self.source.to_string(),
+            rules,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[rstest]
+    #[case(0, true, &[75, 47, 61, 53, 29])]
+    #[case(1, true, &[97, 61, 53, 29, 13])]
+    #[case(2, true, &[75, 29, 13])]
+    #[case(3, false, &[97, 75, 47, 61, 53])]
+    #[case(4, false, &[61, 29, 13])]
+    #[case(5, false, &[97, 75, 47, 29, 13])]
+    fn test_sort(#[case] update: usize, #[case] ordered: bool, #[case] expected: &[u32]) -> miette::Result<()> {
+        let queue = PrintQueue::parse(EXAMPLE)?;
+        let update = &queue.updates[update];
+
+        assert_eq!(ordered, queue.is_ordered(update));
+        assert_eq!(expected, queue.sort(update, Order::Stable)?);
+        assert_eq!(expected, queue.sort(update, Order::Lexicographic)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_without_rules() -> miette::Result<()> {
+        let queue = PrintQueue::parse("5|3\n\n9,5,1,3,7")?;
+        let update = &queue.updates[0];
+
+        assert_eq!(vec![9, 5, 1, 3, 7], queue.sort(update, Order::Stable)?);
+        assert_eq!(vec![1, 5, 3, 7, 9], queue.sort(update, Order::Lexicographic)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycle_outside_update() -> miette::Result<()> {
+        // 1, 2 and 3 go round in a circle but no update has all of them
+        let queue = PrintQueue::parse("1|2\n2|3\n3|1\n\n3,2\n1,3")?;
+
+        assert_eq!(vec![2, 3], queue.sort(&queue.updates[0], Order::Stable)?);
+        assert_eq!(vec![3, 1], queue.sort(&queue.updates[1], Order::Stable)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycle() -> miette::Result<()> {
+        let input = "1|2\n4|5\n2|3\n5|4\n3|1\n\n4,1,2,3";
+        let queue = PrintQueue::parse(input)?;
+
+        let Err(AocError::RuleCycle { update, rules, .. }) = queue.sort(&queue.updates[0], Order::Stable) else {
+            panic!("expected a cycle");
+        };
+
+        assert_eq!("4,1,2,3", update);
+        assert_eq!(
+            vec!["1|2", "2|3", "3|1"],
+            rules.iter().map(|span| &input[span.offset()..span.offset() + span.len()]).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("1-2\n\n1,2", "1-2")]
+    #[case("1|x\n\n1,2", "1|x")]
+    #[case("1|2\n\n1,,2", "1,,2")]
+    #[case("1|2\n\n1,2,1", "1,2,1")]
+    fn test_invalid_line(#[case] input: &str, #[case] line: &str) {
+        let Err(AocError::Core(aoc_core::AocError::ParseError { span, .. })) = PrintQueue::parse(input) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(line, &input[span.offset()..span.offset() + span.len()]);
+    }
+}
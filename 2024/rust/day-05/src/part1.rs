@@ -1,40 +1,16 @@
-use std::collections::{HashMap, HashSet};
-
-use itertools::Itertools;
-
-use crate::{custom_error::AocError, process_update};
+use crate::custom_error::AocError;
+use crate::ordering::{middle, PrintQueue};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (rules, updates) = input.split("\n\n").collect_tuple().unwrap();
-
-    let rules_map = rules.lines()
-        .fold(HashMap::new(), | mut rules, rule | {
-            let (key, value) = rule.split("|").collect_tuple().unwrap();
-
-            rules.entry(key).or_insert(HashSet::new()).insert(value);
-
-            rules
-        });
+    let queue = PrintQueue::parse(input)?;
 
-    let output = updates.lines()
-        .enumerate()
-        .filter_map(|(row, update)| {
-            let segments: Vec<_> = update.split(",").collect();
-            // Check if path is valid by looking at consecutive pairs
-            let is_valid = segments.windows(2).all(|pair| {
-                let (current, next) = (pair[0], pair[1]);
-                rules_map.get(current)
-                    .map_or(false, |rules| rules.contains(next))
-            });
-            
-            match is_valid {
-                true => Some((row, update)),
-                false => None,
-            }
-        })
-        .map(|(_, x)| process_update(x))
-        .sum::<usize>();
+    let output = queue
+        .updates
+        .iter()
+        .filter(|update| queue.is_ordered(update))
+        .map(|update| middle(update))
+        .sum::<u32>();
 
     Ok(output.to_string())
 }
@@ -42,6 +18,7 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::process_update;
 
     use rstest::rstest;
 
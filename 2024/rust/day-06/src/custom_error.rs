@@ -1,9 +1,28 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("there is no guard (^) on the map")]
+    #[diagnostic(code(aoc::missing_guard))]
+    MissingGuard,
+
+    #[error("the guard never leaves the lab")]
+    #[diagnostic(code(aoc::guard_never_leaves))]
+    GuardNeverLeaves,
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::MissingGuard => ErrorKind::InvalidInput,
+            Self::GuardNeverLeaves => ErrorKind::NoSolution,
+        }
+    }
+}
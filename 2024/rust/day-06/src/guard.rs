@@ -0,0 +1,294 @@
+use rayon::prelude::*;
+
+use crate::custom_error::AocError;
+
+/// Up, Right, Down, Left, so turning right is `(dir + 1) % 4`
+const DELTAS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Jump table entry for a guard that walks off the map
+const EXIT: u32 = u32::MAX;
+
+/// One bit per cell, indexed row major
+#[derive(Debug, Clone)]
+pub struct BitGrid {
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { bits: vec![0; (width * height).div_ceil(64)] }
+    }
+
+    pub fn get(&self, idx: usize) -> bool {
+        self.bits[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// Returns whether the bit was newly set
+    pub fn set(&mut self, idx: usize) -> bool {
+        let fresh = !self.get(idx);
+        self.bits[idx / 64] |= 1 << (idx % 64);
+        fresh
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+/// Where an extra obstacle can go, and the state the guard is in right before walking into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate {
+    pub obstacle: usize,
+    pub from: usize,
+    pub dir: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Patrol {
+    pub visited: BitGrid,
+    /// every cell on the route except the start, in the order the guard first reaches them
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Lab {
+    width: usize,
+    height: usize,
+    walls: BitGrid,
+    start: usize,
+    /// for each direction and cell, the cell the guard stops on before the next wall
+    jumps: [Vec<u32>; 4],
+}
+
+impl Lab {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let mut walls = BitGrid::new(width, height);
+        let mut start = None;
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate().take(width) {
+                match cell {
+                    b'#' => {
+                        walls.set(y * width + x);
+                    }
+                    b'^' => start = Some(y * width + x),
+                    _ => {}
+                }
+            }
+        }
+
+        let start = start.ok_or(AocError::MissingGuard)?;
+        let jumps = std::array::from_fn(|dir| Self::jump_table(width, height, &walls, dir));
+
+        Ok(Self { width, height, walls, start, jumps })
+    }
+
+    /// Sweeps against the walking direction, remembering the last cell before a wall
+    fn jump_table(width: usize, height: usize, walls: &BitGrid, dir: usize) -> Vec<u32> {
+        let mut table = vec![EXIT; width * height];
+        let (dx, dy) = DELTAS[dir];
+
+        let lines: Vec<Vec<usize>> = if dx == 0 {
+            (0..width).map(|x| (0..height).map(|y| y * width + x).collect()).collect()
+        } else {
+            (0..height).map(|y| (0..width).map(|x| y * width + x).collect()).collect()
+        };
+
+        for mut line in lines {
+            // walk from the far end the guard is heading towards
+            if dx + dy > 0 {
+                line.reverse();
+            }
+
+            let mut stop = EXIT;
+            let mut after_wall = false;
+            for idx in line {
+                if walls.get(idx) {
+                    after_wall = true;
+                } else {
+                    if after_wall {
+                        stop = idx as u32;
+                        after_wall = false;
+                    }
+                    table[idx] = stop;
+                }
+            }
+        }
+
+        table
+    }
+
+    fn coords(&self, idx: usize) -> (i32, i32) {
+        ((idx % self.width) as i32, (idx / self.width) as i32)
+    }
+
+    fn step(&self, idx: usize, dir: usize) -> Option<usize> {
+        let (x, y) = self.coords(idx);
+        let (dx, dy) = DELTAS[dir];
+        let (x, y) = (x + dx, y + dy);
+
+        (x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height)
+            .then(|| y as usize * self.width + x as usize)
+    }
+
+    /// Where the guard stops walking from `idx` in `dir`, `None` if it leaves the map.
+    /// The precomputed jump is cut short if the extra obstacle sits on the way.
+    fn jump(&self, idx: usize, dir: usize, obstacle: Option<usize>) -> Option<usize> {
+        let stop = self.jumps[dir][idx];
+
+        if let Some(obstacle) = obstacle {
+            let (x, y) = self.coords(idx);
+            let (ox, oy) = self.coords(obstacle);
+            let (dx, dy) = DELTAS[dir];
+
+            let ahead = if dx == 0 {
+                ox == x && (oy - y).signum() == dy
+            } else {
+                oy == y && (ox - x).signum() == dx
+            };
+
+            if ahead {
+                let distance = |(px, py): (i32, i32)| (px - x).abs() + (py - y).abs();
+                if stop == EXIT || distance((ox, oy)) <= distance(self.coords(stop as usize)) {
+                    return Some(((oy - dy) * self.width as i32 + (ox - dx)) as usize);
+                }
+            }
+        }
+
+        (stop != EXIT).then_some(stop as usize)
+    }
+
+    /// Walks the guard cell by cell until it leaves
+    pub fn patrol(&self) -> miette::Result<Patrol, AocError> {
+        let mut visited = BitGrid::new(self.width, self.height);
+        let mut turns = vec![0_u8; self.width * self.height];
+        let mut candidates = Vec::new();
+
+        let (mut idx, mut dir) = (self.start, 0);
+        visited.set(idx);
+
+        while let Some(next) = self.step(idx, dir) {
+            if self.walls.get(next) {
+                if turns[idx] & (1 << dir) != 0 {
+                    return Err(AocError::GuardNeverLeaves);
+                }
+                turns[idx] |= 1 << dir;
+                dir = (dir + 1) % 4;
+                continue;
+            }
+
+            if visited.set(next) {
+                candidates.push(Candidate { obstacle: next, from: idx, dir });
+            }
+            idx = next;
+        }
+
+        Ok(Patrol { visited, candidates })
+    }
+
+    /// Whether the guard gets stuck in a loop once `candidate.obstacle` is added,
+    /// picking the route back up from just before the guard would first walk into it
+    pub fn loops_with(&self, candidate: &Candidate) -> bool {
+        let mut seen = vec![0_u8; self.width * self.height];
+        let (mut idx, mut dir) = (candidate.from, candidate.dir);
+
+        while let Some(stop) = self.jump(idx, dir, Some(candidate.obstacle)) {
+            if seen[stop] & (1 << dir) != 0 {
+                return true;
+            }
+            seen[stop] |= 1 << dir;
+
+            idx = stop;
+            dir = (dir + 1) % 4;
+        }
+
+        false
+    }
+
+    /// Every cell an obstacle could be placed on to trap the guard
+    pub fn loop_obstacles(&self) -> miette::Result<Vec<usize>, AocError> {
+        let patrol = self.patrol()?;
+
+        Ok(patrol
+            .candidates
+            .par_iter()
+            .filter(|candidate| self.loops_with(candidate))
+            .map(|candidate| candidate.obstacle)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn test_patrol() -> miette::Result<()> {
+        let lab = Lab::parse(EXAMPLE)?;
+
+        assert_eq!(41, lab.patrol()?.visited.count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_loop_obstacles() -> miette::Result<()> {
+        let lab = Lab::parse(EXAMPLE)?;
+
+        let mut obstacles = lab
+            .loop_obstacles()?
+            .into_iter()
+            .map(|idx| (idx % 10, idx / 10))
+            .collect::<Vec<_>>();
+        obstacles.sort();
+
+        assert_eq!(vec![(1, 8), (3, 6), (3, 8), (6, 7), (7, 7), (7, 9)], obstacles);
+        Ok(())
+    }
+
+    #[test]
+    fn test_jump_tables() -> miette::Result<()> {
+        let lab = Lab::parse(EXAMPLE)?;
+        let start = 6 * 10 + 4;
+
+        // straight up to just below the wall at (4, 0)
+        assert_eq!(Some(14), lab.jump(start, 0, None));
+        // an obstacle in the way cuts the jump short
+        assert_eq!(Some(44), lab.jump(start, 0, Some(34)));
+        // right in front of the guard it just turns
+        assert_eq!(Some(start), lab.jump(start, 0, Some(54)));
+        // behind the guard it doesn't matter
+        assert_eq!(Some(14), lab.jump(start, 0, Some(74)));
+        // walking off the map, unless something is placed in the way
+        assert_eq!(None, lab.jump(start, 2, None));
+        assert_eq!(Some(84), lab.jump(start, 2, Some(94)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_never_leaves() -> miette::Result<()> {
+        let lab = Lab::parse(".#..\n...#\n#^..\n..#.")?;
+
+        assert!(matches!(lab.patrol(), Err(AocError::GuardNeverLeaves)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_guard() {
+        assert!(matches!(Lab::parse("..#\n..."), Err(AocError::MissingGuard)));
+    }
+}
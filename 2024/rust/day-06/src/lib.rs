@@ -2,5 +2,6 @@
 
 pub mod custom_error;
 
+pub mod guard;
 pub mod part1;
 pub mod part2;
@@ -0,0 +1,283 @@
+use crate::custom_error::AocError;
+
+/// A binary operator the engineers might have lost, evaluated strictly left to right.
+///
+/// Solving runs right to left, so besides applying an operator we need to undo it:
+/// given the result and the right operand, which left operand produced it?
+pub trait Operator: std::fmt::Debug {
+    fn symbol(&self) -> &'static str;
+
+    /// `left op right`, `None` on overflow
+    fn apply(&self, left: u64, right: u64) -> Option<u64>;
+
+    /// The `left` with `left op right == result`, `None` if there isn't one.
+    /// Operands are assumed to be positive, like the puzzle input.
+    fn unapply(&self, result: u64, right: u64) -> Option<u64>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Add;
+
+impl Operator for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_add(right)
+    }
+
+    fn unapply(&self, result: u64, right: u64) -> Option<u64> {
+        result.checked_sub(right)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Multiply;
+
+impl Operator for Multiply {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(right)
+    }
+
+    fn unapply(&self, result: u64, right: u64) -> Option<u64> {
+        (right != 0 && result % right == 0).then(|| result / right)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Concatenate;
+
+impl Concatenate {
+    /// 10^(number of digits in n)
+    fn shift(n: u64) -> Option<u64> {
+        10_u64.checked_pow(n.checked_ilog10().unwrap_or(0) + 1)
+    }
+}
+
+impl Operator for Concatenate {
+    fn symbol(&self) -> &'static str {
+        "||"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(Self::shift(right)?)?.checked_add(right)
+    }
+
+    /// strips the digits of `right` off the end of `result`
+    fn unapply(&self, result: u64, right: u64) -> Option<u64> {
+        let shift = Self::shift(right)?;
+        (result % shift == right).then(|| result / shift)
+    }
+}
+
+pub const PART1: &[&dyn Operator] = &[&Add, &Multiply];
+pub const PART2: &[&dyn Operator] = &[&Add, &Multiply, &Concatenate];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub target: u64,
+    pub numbers: Vec<u64>,
+}
+
+impl Equation {
+    /// Evaluates the numbers left to right with the given operators
+    pub fn evaluate(&self, ops: &[&dyn Operator]) -> Option<u64> {
+        let (first, rest) = self.numbers.split_first()?;
+        if ops.len() != rest.len() {
+            return None;
+        }
+
+        rest.iter().zip(ops).try_fold(*first, |acc, (&n, op)| op.apply(acc, n))
+    }
+
+    /// Peels numbers off the right, keeping only the branches where the
+    /// last operator can actually be undone
+    fn solve<'a>(
+        target: u64,
+        numbers: &[u64],
+        operators: &[&'a dyn Operator],
+        path: &mut Vec<&'a dyn Operator>,
+        found: &mut Vec<Vec<&'a dyn Operator>>,
+        limit: usize,
+    ) {
+        if found.len() >= limit {
+            return;
+        }
+
+        match numbers {
+            [] => {}
+            [first] => {
+                if *first == target {
+                    found.push(path.iter().rev().copied().collect());
+                }
+            }
+            [rest @ .., last] => {
+                for &op in operators {
+                    if let Some(left) = op.unapply(target, *last) {
+                        path.push(op);
+                        Self::solve(left, rest, operators, path, found, limit);
+                        path.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    fn solve_up_to<'a>(&self, operators: &[&'a dyn Operator], limit: usize) -> Vec<Vec<&'a dyn Operator>> {
+        let mut found = Vec::new();
+        Self::solve(self.target, &self.numbers, operators, &mut Vec::new(), &mut found, limit);
+        found
+    }
+
+    /// One operator sequence (left to right) that makes the equation true
+    pub fn solution<'a>(&self, operators: &[&'a dyn Operator]) -> Option<Vec<&'a dyn Operator>> {
+        self.solve_up_to(operators, 1).pop()
+    }
+
+    /// Every operator sequence (left to right) that makes the equation true
+    pub fn solutions<'a>(&self, operators: &[&'a dyn Operator]) -> Vec<Vec<&'a dyn Operator>> {
+        self.solve_up_to(operators, usize::MAX)
+    }
+
+    pub fn is_solvable(&self, operators: &[&dyn Operator]) -> bool {
+        self.solution(operators).is_some()
+    }
+
+    /// e.g. `190 = 10 * 19`
+    pub fn render(&self, ops: &[&dyn Operator]) -> String {
+        let mut rendered = self.target.to_string() + " =";
+
+        for (i, n) in self.numbers.iter().enumerate() {
+            if let Some(op) = i.checked_sub(1).and_then(|i| ops.get(i)) {
+                rendered += " ";
+                rendered += op.symbol();
+            }
+            rendered += &format!(" {n}");
+        }
+
+        rendered
+    }
+}
+
+pub fn parse_equations(input: &str) -> miette::Result<Vec<Equation>, AocError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let invalid = || AocError::parse_at(input, line, "expected an equation like 190: 10 19");
+
+            let (target, numbers) = line.split_once(':').ok_or_else(invalid)?;
+            let target = target.trim().parse().map_err(|_| invalid())?;
+            let numbers = numbers
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if numbers.is_empty() {
+                return Err(invalid());
+            }
+
+            Ok(Equation { target, numbers })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn equation(line: &str) -> Equation {
+        parse_equations(line).unwrap().remove(0)
+    }
+
+    #[rstest]
+    #[case("190: 10 19", vec!["190 = 10 * 19"])]
+    #[case("3267: 81 40 27", vec!["3267 = 81 * 40 + 27", "3267 = 81 + 40 * 27"])]
+    #[case("292: 11 6 16 20", vec!["292 = 11 + 6 * 16 + 20"])]
+    #[case("83: 17 5", vec![])]
+    #[case("156: 15 6", vec![])]
+    fn test_part1_solutions(#[case] line: &str, #[case] expected: Vec<&str>) {
+        let equation = equation(line);
+        let solutions = equation.solutions(PART1);
+
+        assert_eq!(expected, solutions.iter().map(|ops| equation.render(ops)).collect::<Vec<_>>());
+        for ops in solutions {
+            assert_eq!(Some(equation.target), equation.evaluate(&ops));
+        }
+    }
+
+    #[rstest]
+    #[case("156: 15 6", "156 = 15 || 6")]
+    #[case("7290: 6 8 6 15", "7290 = 6 * 8 || 6 * 15")]
+    #[case("192: 17 8 14", "192 = 17 || 8 + 14")]
+    fn test_part2_solution(#[case] line: &str, #[case] expected: &str) {
+        let equation = equation(line);
+        let ops = equation.solution(PART2).unwrap();
+
+        assert_eq!(expected, equation.render(&ops));
+        assert_eq!(Some(equation.target), equation.evaluate(&ops));
+        assert!(!equation.is_solvable(PART1));
+    }
+
+    #[rstest]
+    #[case(&Add, 12, 34, 46)]
+    #[case(&Multiply, 12, 34, 408)]
+    #[case(&Concatenate, 12, 34, 1234)]
+    #[case(&Concatenate, 12, 0, 120)]
+    #[case(&Concatenate, 0, 7, 7)]
+    fn test_unapply(#[case] op: &dyn Operator, #[case] left: u64, #[case] right: u64, #[case] result: u64) {
+        assert_eq!(Some(result), op.apply(left, right));
+        assert_eq!(Some(left), op.unapply(result, right));
+    }
+
+    #[test]
+    fn test_unapply_prunes() {
+        assert_eq!(None, Add.unapply(5, 6));
+        assert_eq!(None, Multiply.unapply(10, 3));
+        assert_eq!(None, Concatenate.unapply(1234, 35));
+        assert_eq!(None, Concatenate.unapply(34, 1234));
+    }
+
+    /// A new operator set only needs the trait
+    #[derive(Debug)]
+    struct Subtract;
+
+    impl Operator for Subtract {
+        fn symbol(&self) -> &'static str {
+            "-"
+        }
+
+        fn apply(&self, left: u64, right: u64) -> Option<u64> {
+            left.checked_sub(right)
+        }
+
+        fn unapply(&self, result: u64, right: u64) -> Option<u64> {
+            result.checked_add(right)
+        }
+    }
+
+    #[test]
+    fn test_custom_operator() {
+        let equation = equation("4: 10 3 3");
+        let ops = equation.solution(&[&Add, &Subtract]).unwrap();
+
+        assert_eq!("4 = 10 - 3 - 3", equation.render(&ops));
+    }
+
+    #[test]
+    fn test_invalid_equation() {
+        for (input, line) in [("190 10 19", (0, 9)), ("3: 1 2\n190:", (7, 4)), ("3: 1 x", (0, 6))] {
+            let Err(AocError::ParseError { span, .. }) = parse_equations(input) else {
+                panic!("expected a parse error for {input:?}");
+            };
+            assert_eq!(line, (span.offset(), span.len()));
+        }
+    }
+}
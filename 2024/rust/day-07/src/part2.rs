@@ -1,81 +1,16 @@
-use tracing_subscriber::fmt::format;
-
-use crate::custom_error::AocError;
-
-enum Operator {
-    /// +
-    Add,
-    /// ||
-    Concatenate,
-    /// *
-    Multiply,
-}
-
-fn try_evaluate(numbers: &[usize], expected: usize) -> Option<usize> {
-    let mut ops: Vec<Operator> = Vec::new();
-
-    evaluate(numbers[0], &numbers[1..], expected, &mut ops)
-}
-
-fn evaluate(current: usize, nums: &[usize], target: usize, ops: &mut Vec<Operator>) -> Option<usize> {
-    // dbg!(current, target);
-    if current > target {
-        return None;
-    }
-    
-    // Base case - when we've used all numbers
-    if nums.is_empty() {
-        return if current == target { Some(current) } else { None }
-    }
-
-    let n = nums[0];
-    let rest = &nums[1..];
-
-    // let concatenation = dbg!(rest.iter().fold(String::new(), |acc, n| format!("{}{}", acc, n))).parse();
-    let concatenation = format!("{}{}", current, n).parse::<usize>().unwrap();
-
-    // Try string concatenation (length)
-    if let Some(result) = evaluate(concatenation, rest, target, ops) {
-        ops.push(Operator::Concatenate);
-        return Some(result);
-    }
-
-    // Try multiplication next (faster growth)
-    if let Some(result) = evaluate(current * n, rest, target, ops) {
-        ops.push(Operator::Multiply);
-        return Some(result);
-    }
-
-    // Try addition if multiplication failed
-    if let Some(result) = evaluate(current + n, rest, target, ops) {
-        ops.push(Operator::Add);
-        return Some(result);
-    }
-
-    None
-}
+use crate::{custom_error::AocError, equations::{parse_equations, PART2}};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let output = input
-        .lines()
-        .filter_map(|line| {
-            let(expected, operation) = line.split_once(": ").unwrap();
-
-            // dbg!(expected, operation);
-
-            let nums = operation.split_whitespace()
-                .map(|num| num.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-
-            // dbg!(expected, &nums); 
-
-            try_evaluate(&nums, expected.parse::<usize>().unwrap())
-        })
-        .sum::<usize>();
+    let output = parse_equations(input)?
+        .iter()
+        .filter(|equation| equation.is_solvable(PART2))
+        .map(|equation| equation.target)
+        .sum::<u64>();
 
     Ok(output.to_string())
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,17 +18,15 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case("156: 15 6", "true")] // 15 || 6 = 156
-    #[case("7290: 6 8 6 15", "true")] // 6 * 8 || 6 * 15
-    #[case("192: 17 8 14", "true")] // 17 || 8 + 14
-    fn test_cases(#[case] input: &str, #[case] _expected: &str) {
-        let(expected, input) = input.split_once(": ").unwrap();
-        let expected = expected.parse::<usize>().unwrap();
-        let nums = input.split_whitespace().map(|num| num.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
-
-        // assert_eq!(try_evaluate(nums, expected).unwrap(), expected);
-        assert!(try_evaluate(&nums, expected).is_some());
+    #[case("156: 15 6", true)] // 15 || 6 = 156
+    #[case("7290: 6 8 6 15", true)] // 6 * 8 || 6 * 15
+    #[case("192: 17 8 14", true)] // 17 || 8 + 14
+    #[case("83: 17 5", false)]
+    #[case("161011: 16 10 13", false)]
+    fn test_cases(#[case] input: &str, #[case] expected: bool) {
+        let equation = parse_equations(input).unwrap().remove(0);
+
+        assert_eq!(expected, equation.is_solvable(PART2));
     }
 
     #[test]
@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use aoc_math::gcd;
+use glam::IVec2;
+
+/// ANSI colours handed out to frequencies in order, wrapping around
+const PALETTE: [u8; 12] = [196, 46, 33, 226, 201, 51, 208, 118, 99, 214, 45, 161];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// part 1: points in line with two antennas where one is twice as far away as the other
+    TwiceDistance,
+    /// part 2: every point in line with two antennas
+    ResonantHarmonics,
+}
+
+#[derive(Debug, Clone)]
+pub struct City {
+    pub size: IVec2,
+    /// sorted by frequency so colours and output are stable
+    pub antennas: BTreeMap<char, Vec<IVec2>>,
+}
+
+impl City {
+    /// Anything but `.` and `#` (the examples' antinode marks) is an antenna
+    pub fn parse(input: &str) -> Self {
+        let mut antennas: BTreeMap<char, Vec<IVec2>> = BTreeMap::new();
+        let mut size = IVec2::ZERO;
+
+        for (y, line) in input.lines().enumerate() {
+            size.y = y as i32 + 1;
+            size.x = size.x.max(line.chars().count() as i32);
+
+            for (x, c) in line.chars().enumerate() {
+                if c != '.' && c != '#' {
+                    antennas.entry(c).or_default().push(IVec2::new(x as i32, y as i32));
+                }
+            }
+        }
+
+        Self { size, antennas }
+    }
+
+    fn in_bounds(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && pos.x < self.size.x && pos.y < self.size.y
+    }
+
+    /// Antinodes made by one pair of antennas.
+    ///
+    /// The delta is divided by its gcd, so `a + k * step` walks every grid point on the
+    /// line and the antennas sit at k = 0 and k = g. One is twice as far as the other at
+    /// k = -g and k = 2g, and also at g/3 and 2g/3 when those land on the grid.
+    fn pair(&self, a: IVec2, b: IVec2, rule: Rule) -> Vec<IVec2> {
+        let delta = b - a;
+        let g = gcd(delta.x, delta.y);
+        if g == 0 {
+            return Vec::new();
+        }
+        let step = delta / g;
+
+        match rule {
+            Rule::TwiceDistance => {
+                let mut ks = vec![-g, 2 * g];
+                if g % 3 == 0 {
+                    ks.extend([g / 3, 2 * g / 3]);
+                }
+
+                ks.into_iter().map(|k| a + step * k).filter(|&pos| self.in_bounds(pos)).collect()
+            }
+            Rule::ResonantHarmonics => {
+                let mut start = a;
+                while self.in_bounds(start - step) {
+                    start -= step;
+                }
+
+                std::iter::successors(Some(start), |&pos| Some(pos + step))
+                    .take_while(|&pos| self.in_bounds(pos))
+                    .collect()
+            }
+        }
+    }
+
+    /// Antinodes for each frequency, they may overlap between frequencies
+    pub fn antinodes(&self, rule: Rule) -> BTreeMap<char, HashSet<IVec2>> {
+        self.antennas
+            .iter()
+            .map(|(&frequency, antennas)| {
+                let antinodes = antennas
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &a)| antennas[i + 1..].iter().flat_map(move |&b| self.pair(a, b, rule)))
+                    .collect();
+
+                (frequency, antinodes)
+            })
+            .collect()
+    }
+
+    pub fn unique_antinodes(&self, rule: Rule) -> HashSet<IVec2> {
+        self.antinodes(rule).into_values().flatten().collect()
+    }
+
+    /// The map with antinodes drawn as `#` underneath the antennas, like the puzzle
+    /// examples. With `colour` each frequency and its antinodes get their own ANSI colour.
+    pub fn render(&self, rule: Rule, colour: bool) -> String {
+        let colours: HashMap<char, u8> = self
+            .antennas
+            .keys()
+            .zip(PALETTE.iter().cycle())
+            .map(|(&frequency, &code)| (frequency, code))
+            .collect();
+
+        let mut cells: HashMap<IVec2, (char, char)> = HashMap::new();
+        for (frequency, antinodes) in self.antinodes(rule) {
+            for pos in antinodes {
+                cells.entry(pos).or_insert(('#', frequency));
+            }
+        }
+        for (&frequency, antennas) in &self.antennas {
+            for &pos in antennas {
+                cells.insert(pos, (frequency, frequency));
+            }
+        }
+
+        let mut output = String::new();
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                match cells.get(&IVec2::new(x, y)) {
+                    Some(&(c, frequency)) if colour => {
+                        output += &format!("\x1b[38;5;{}m{c}\x1b[0m", colours[&frequency]);
+                    }
+                    Some(&(c, _)) => output.push(c),
+                    None => output.push('.'),
+                }
+            }
+            if y + 1 < self.size.y {
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+    #[test]
+    fn test_render_twice_distance() {
+        let city = City::parse(EXAMPLE);
+
+        assert_eq!(
+            "......#....#
+...#....0...
+....#0....#.
+..#....0....
+....0....#..
+.#....A.....
+...#........
+#......#....
+........A...
+.........A..
+..........#.
+..........#.",
+            city.render(Rule::TwiceDistance, false)
+        );
+    }
+
+    #[test]
+    fn test_render_resonant_harmonics() {
+        let city = City::parse("T.........
+...T......
+.T........
+..........
+..........
+..........
+..........
+..........
+..........
+..........");
+
+        assert_eq!(
+            "T....#....
+...T......
+.T....#...
+.........#
+..#.......
+..........
+...#......
+..........
+....#.....
+..........",
+            city.render(Rule::ResonantHarmonics, false)
+        );
+        assert_eq!(9, city.unique_antinodes(Rule::ResonantHarmonics).len());
+    }
+
+    #[test]
+    fn test_lattice_points_between_multiples() {
+        let city = City::parse("a....\n.....\n..a..\n.....\n.....");
+
+        // the raw delta (2, 2) would skip (1, 1) and (3, 3)
+        assert_eq!(5, city.unique_antinodes(Rule::ResonantHarmonics).len());
+        assert_eq!(HashSet::from([IVec2::new(4, 4)]), city.unique_antinodes(Rule::TwiceDistance));
+    }
+
+    #[test]
+    fn test_twice_distance_between_antennas() {
+        let city = City::parse("a.........\n..........\n..........\n...a......\n..........\n..........\n..........\n..........\n..........\n..........");
+
+        assert_eq!(
+            HashSet::from([IVec2::new(1, 1), IVec2::new(2, 2), IVec2::new(6, 6)]),
+            city.unique_antinodes(Rule::TwiceDistance)
+        );
+    }
+
+    #[test]
+    fn test_render_colour() {
+        let city = City::parse(EXAMPLE);
+        let rendered = city.render(Rule::TwiceDistance, true);
+
+        // '0' is handed the first colour, 'A' the second
+        assert!(rendered.contains("\x1b[38;5;196m0\x1b[0m"));
+        assert!(rendered.contains("\x1b[38;5;46mA\x1b[0m"));
+        assert_eq!(12, rendered.lines().count());
+    }
+}
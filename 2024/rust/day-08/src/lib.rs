@@ -1,4 +1,5 @@
 pub mod custom_error;
 
+pub mod antinodes;
 pub mod part1;
 pub mod part2;
\ No newline at end of file
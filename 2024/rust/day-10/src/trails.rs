@@ -0,0 +1,261 @@
+use glam::IVec2;
+
+use crate::custom_error::AocError;
+
+const DIRECTIONS: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// The lowest and highest height of a hiking trail
+pub const TRAILHEAD: u8 = 0;
+pub const PEAK: u8 = 9;
+
+/// A grid where every step goes up by exactly one, which makes it a DAG ordered by height
+#[derive(Debug, Clone)]
+pub struct TopoMap {
+    width: usize,
+    height: usize,
+    /// row major, `None` for impassable `.` cells
+    heights: Vec<Option<u8>>,
+}
+
+/// What a single start cell can reach
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trailhead {
+    pub position: IVec2,
+    /// distinct sinks reachable
+    pub score: usize,
+    /// distinct paths to any sink
+    pub rating: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrailReport {
+    /// in reading order
+    pub trailheads: Vec<Trailhead>,
+}
+
+impl TrailReport {
+    pub fn total_score(&self) -> usize {
+        self.trailheads.iter().map(|trailhead| trailhead.score).sum()
+    }
+
+    pub fn total_rating(&self) -> u64 {
+        self.trailheads.iter().map(|trailhead| trailhead.rating).sum()
+    }
+}
+
+impl TopoMap {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        let rows = input.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        let mut heights = vec![None; width * height];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, (i, c)) in row.char_indices().enumerate().take(width) {
+                heights[y * width + x] = match c {
+                    '.' => None,
+                    c => Some(c.to_digit(10).ok_or_else(|| {
+                        let message = format!("{c:?} is not a height, expected a digit or '.'");
+                        AocError::parse_at(input, &row[i..i + c.len_utf8()], message)
+                    })? as u8),
+                };
+            }
+        }
+
+        Ok(Self { width, height, heights })
+    }
+
+    fn position(&self, idx: usize) -> IVec2 {
+        IVec2::new((idx % self.width) as i32, (idx / self.width) as i32)
+    }
+
+    fn index(&self, pos: IVec2) -> Option<usize> {
+        (pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    fn neighbours(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let pos = self.position(idx);
+
+        DIRECTIONS.into_iter().filter_map(move |dir| self.index(pos + dir))
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<u8> {
+        self.heights[self.index(pos)?]
+    }
+
+    /// Scores and ratings for every trail from `low` up to `high`.
+    ///
+    /// Cells are handled a whole height at a time from the top down, so each one only
+    /// looks at its neighbours one higher: the number of paths is their sum and the
+    /// reachable sinks are the union of their bitsets.
+    pub fn paths(&self, low: u8, high: u8) -> TrailReport {
+        if low > high {
+            return TrailReport { trailheads: Vec::new() };
+        }
+
+        let mut layers = vec![Vec::new(); (high - low) as usize + 1];
+        let mut sinks = 0_usize;
+        let mut sink_bit = vec![0; self.heights.len()];
+
+        for (idx, &cell) in self.heights.iter().enumerate() {
+            match cell {
+                Some(h) if (low..=high).contains(&h) => {
+                    if h == high {
+                        sink_bit[idx] = sinks;
+                        sinks += 1;
+                    }
+                    layers[(h - low) as usize].push(idx);
+                }
+                _ => {}
+            }
+        }
+
+        let words = sinks.div_ceil(64);
+        let mut ratings = vec![0_u64; self.heights.len()];
+        let mut reachable = vec![Vec::new(); self.heights.len()];
+
+        for &idx in &layers[(high - low) as usize] {
+            let bit = sink_bit[idx];
+            ratings[idx] = 1;
+            reachable[idx] = vec![0_u64; words];
+            reachable[idx][bit / 64] |= 1 << (bit % 64);
+        }
+
+        for h in (low..high).rev() {
+            for &idx in &layers[(h - low) as usize] {
+                let mut rating = 0;
+                let mut sinks = vec![0_u64; words];
+
+                for next in self.neighbours(idx) {
+                    if self.heights[next] == Some(h + 1) {
+                        rating += ratings[next];
+                        sinks.iter_mut().zip(&reachable[next]).for_each(|(a, b)| *a |= b);
+                    }
+                }
+
+                ratings[idx] = rating;
+                reachable[idx] = sinks;
+            }
+
+            // the layer above isn't needed any more
+            for &idx in &layers[(h + 1 - low) as usize] {
+                reachable[idx] = Vec::new();
+            }
+        }
+
+        let trailheads = layers[0]
+            .iter()
+            .map(|&idx| Trailhead {
+                position: self.position(idx),
+                score: reachable[idx].iter().map(|word| word.count_ones() as usize).sum(),
+                rating: ratings[idx],
+            })
+            .collect();
+
+        TrailReport { trailheads }
+    }
+
+    /// Every hiking trail, from height 0 to 9
+    pub fn trails(&self) -> TrailReport {
+        self.paths(TRAILHEAD, PEAK)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+    #[test]
+    fn test_breakdown() -> miette::Result<()> {
+        let report = TopoMap::parse(EXAMPLE)?.trails();
+
+        assert_eq!(
+            vec![5, 6, 5, 3, 1, 3, 5, 3, 5],
+            report.trailheads.iter().map(|trailhead| trailhead.score).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![20, 24, 10, 4, 1, 4, 5, 8, 5],
+            report.trailheads.iter().map(|trailhead| trailhead.rating).collect::<Vec<_>>()
+        );
+        assert_eq!(IVec2::new(2, 0), report.trailheads[0].position);
+        assert_eq!(36, report.total_score());
+        assert_eq!(81, report.total_rating());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9", 2, 2)]
+    #[case("..90..9
+...1.98
+...2..7
+6543456
+765.987
+876....
+987....", 4, 13)]
+    #[case("10..9..
+2...8..
+3...7..
+4567654
+...8..3
+...9..2
+.....01", 3, 3)]
+    #[case(".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....", 1, 3)]
+    #[case("012345
+123456
+234567
+345678
+4.6789
+56789.", 2, 227)]
+    fn test_impassable_cells(#[case] input: &str, #[case] score: usize, #[case] rating: u64) -> miette::Result<()> {
+        let report = TopoMap::parse(input)?.trails();
+
+        assert_eq!(score, report.total_score());
+        assert_eq!(rating, report.total_rating());
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_heights() -> miette::Result<()> {
+        let map = TopoMap::parse("0123\n1234\n2345")?;
+
+        // every 3 reaches the single 5, the middle one along two paths
+        let report = map.paths(3, 5);
+        assert_eq!(3, report.trailheads.len());
+        assert_eq!(Some(3), map.get(IVec2::new(3, 0)));
+        assert_eq!(vec![(1, 1), (1, 2), (1, 1)], report.trailheads.iter().map(|t| (t.score, t.rating)).collect::<Vec<_>>());
+        assert!(map.paths(5, 3).trailheads.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_height() {
+        let Err(AocError::ParseError { span, .. }) = TopoMap::parse("01\n2x") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((4, 1), (span.offset(), span.len()));
+    }
+}
@@ -8,7 +8,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 
     let mut group = c.benchmark_group("day_11::part1");
     group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input))
+        b.iter(|| part1::process(input, 25))
     });
 
     group.finish();
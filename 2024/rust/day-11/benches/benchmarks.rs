@@ -9,7 +9,7 @@ fn main() {
 fn part1() {
     part1::process(divan::black_box(include_str!(
         "../input1.txt",
-    )))
+    )), 25)
     .unwrap();
 }
 
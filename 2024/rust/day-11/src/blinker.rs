@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use crate::custom_error::AocError;
+
+/// Stones are engraved with numbers that (so far) always fit in a u64
+pub type Stone = u64;
+
+pub fn parse_stones(input: &str) -> miette::Result<Vec<Stone>, AocError> {
+    input
+        .split_whitespace()
+        .map(|s| {
+            s.parse::<Stone>()
+                .map_err(|_| aoc_core::AocError::parse_at(input, s, format!("stone {s:?} is not a number")).into())
+        })
+        .collect()
+}
+
+/// Number of decimal digits, without going through `to_string()`
+pub fn digit_count(stone: Stone) -> u32 {
+    stone.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// What a single stone turns into after one blink
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blink {
+    One(Stone),
+    Split(Stone, Stone),
+}
+
+/// An odd number of digits gets multiplied by 2024, which runs out of u64 from
+/// 15 digits up
+pub fn blink_stone(stone: Stone) -> miette::Result<Blink, AocError> {
+    if stone == 0 {
+        return Ok(Blink::One(1));
+    }
+
+    let digits = digit_count(stone);
+    if digits % 2 == 0 {
+        let divisor = 10_u64.pow(digits / 2);
+        Ok(Blink::Split(stone / divisor, stone % divisor))
+    } else {
+        stone.checked_mul(2024).map(Blink::One).ok_or(AocError::StoneOverflow(stone))
+    }
+}
+
+/// Anything that can hold a (potentially very large) number of stones.
+///
+/// `usize` overflows a little after 75 blinks on real inputs, `u128` buys
+/// a lot more headroom and `BigUint` (feature `big-int`) never runs out.
+pub trait StoneCount: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_stone_count {
+    ($($t:ty),*) => {
+        $(impl StoneCount for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn one() -> Self {
+                1
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+        })*
+    };
+}
+
+impl_stone_count!(usize, u64, u128);
+
+#[cfg(feature = "big-int")]
+impl StoneCount for num_bigint::BigUint {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn one() -> Self {
+        Self::from(1_u8)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+/// Counts stones for any number of blinks, memoising `(stone, blinks left)`
+#[derive(Debug)]
+pub struct Blinker<C> {
+    memo: HashMap<(Stone, usize), C>,
+}
+
+impl<C: StoneCount> Default for Blinker<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: StoneCount> Blinker<C> {
+    pub fn new() -> Self {
+        Self { memo: HashMap::new() }
+    }
+
+    fn known(&self, stone: Stone, blinks: usize) -> Option<C> {
+        if blinks == 0 {
+            Some(C::one())
+        } else {
+            self.memo.get(&(stone, blinks)).cloned()
+        }
+    }
+
+    /// How many stones a single stone becomes after `blinks` blinks.
+    ///
+    /// Works through an explicit stack rather than recursing, so a huge `blinks`
+    /// can't run out of call stack, only out of room in `C`.
+    pub fn count(&mut self, stone: Stone, blinks: usize) -> miette::Result<C, AocError> {
+        let overflow = || AocError::CountOverflow(blinks);
+
+        // `Some(blink)` once the stone's children are on the stack below it
+        let mut stack = vec![(stone, blinks, None)];
+
+        while let Some((stone, left, blink)) = stack.pop() {
+            if self.known(stone, left).is_some() {
+                continue;
+            }
+
+            match blink {
+                None => {
+                    let blink = blink_stone(stone)?;
+                    stack.push((stone, left, Some(blink)));
+                    match blink {
+                        Blink::One(next) => stack.push((next, left - 1, None)),
+                        Blink::Split(a, b) => stack.extend([(a, left - 1, None), (b, left - 1, None)]),
+                    }
+                }
+                Some(blink) => {
+                    let count = match blink {
+                        Blink::One(next) => self.known(next, left - 1),
+                        Blink::Split(a, b) => {
+                            let a = self.known(a, left - 1);
+                            let b = self.known(b, left - 1);
+                            a.zip(b).and_then(|(a, b)| a.checked_add(&b))
+                        }
+                    }
+                    .ok_or_else(overflow)?;
+                    self.memo.insert((stone, left), count);
+                }
+            }
+        }
+
+        Ok(self.known(stone, blinks).expect("counted above"))
+    }
+
+    pub fn count_all(&mut self, stones: &[Stone], blinks: usize) -> miette::Result<C, AocError> {
+        stones.iter().try_fold(C::zero(), |total, &stone| {
+            let count = self.count(stone, blinks)?;
+            total.checked_add(&count).ok_or(AocError::CountOverflow(blinks))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlinkReport<C> {
+    pub total: C,
+    /// distinct engraved values after each blink, index 0 is the initial arrangement
+    pub distinct: Vec<usize>,
+}
+
+/// Blinks the whole arrangement at once, keeping one count per distinct value
+pub fn report<C: StoneCount>(stones: &[Stone], blinks: usize) -> miette::Result<BlinkReport<C>, AocError> {
+    let overflow = || AocError::CountOverflow(blinks);
+
+    let mut counts: HashMap<Stone, C> = HashMap::new();
+    for &stone in stones {
+        let entry = counts.entry(stone).or_insert_with(C::zero);
+        *entry = entry.checked_add(&C::one()).ok_or_else(overflow)?;
+    }
+
+    let mut distinct = Vec::with_capacity(blinks + 1);
+    distinct.push(counts.len());
+
+    for _ in 0..blinks {
+        let mut next: HashMap<Stone, C> = HashMap::with_capacity(counts.len());
+
+        for (stone, count) in counts {
+            let mut add = |stone: Stone| -> miette::Result<(), AocError> {
+                let entry = next.entry(stone).or_insert_with(C::zero);
+                *entry = entry.checked_add(&count).ok_or_else(overflow)?;
+                Ok(())
+            };
+
+            match blink_stone(stone)? {
+                Blink::One(stone) => add(stone)?,
+                Blink::Split(left, right) => {
+                    add(left)?;
+                    add(right)?;
+                }
+            }
+        }
+
+        distinct.push(next.len());
+        counts = next;
+    }
+
+    let total = counts
+        .values()
+        .try_fold(C::zero(), |total, count| total.checked_add(count))
+        .ok_or_else(overflow)?;
+
+    Ok(BlinkReport { total, distinct })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(9, 1)]
+    #[case(10, 2)]
+    #[case(2024, 4)]
+    #[case(u64::MAX, 20)]
+    fn test_digit_count(#[case] stone: Stone, #[case] expected: u32) {
+        assert_eq!(expected, digit_count(stone));
+    }
+
+    #[rstest]
+    #[case(0, Blink::One(1))]
+    #[case(1, Blink::One(2024))]
+    #[case(10, Blink::Split(1, 0))]
+    #[case(99, Blink::Split(9, 9))]
+    #[case(999, Blink::One(2021976))]
+    #[case(1000, Blink::Split(10, 0))]
+    fn test_blink_stone(#[case] stone: Stone, #[case] expected: Blink) {
+        assert_eq!(expected, blink_stone(stone).unwrap());
+    }
+
+    #[rstest]
+    #[case("10000000000000001", 0)]
+    #[case("9999999999999999999", 0)]
+    // fits once, but that makes a 17 digit stone that doesn't
+    #[case("9999999999999", 1)]
+    fn test_stone_overflow(#[case] input: &str, #[case] blinks: usize) -> miette::Result<()> {
+        let stones = parse_stones(input)?;
+
+        assert!(matches!(
+            Blinker::<u128>::new().count_all(&stones, blinks + 1),
+            Err(AocError::StoneOverflow(_))
+        ));
+        assert!(matches!(report::<u128>(&stones, blinks + 1), Err(AocError::StoneOverflow(_))));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(1, 3)]
+    #[case(2, 4)]
+    #[case(3, 5)]
+    #[case(4, 9)]
+    #[case(5, 13)]
+    #[case(6, 22)]
+    #[case(25, 55312)]
+    fn test_blinker(#[case] blinks: usize, #[case] expected: usize) -> miette::Result<()> {
+        let stones = parse_stones("125 17")?;
+
+        assert_eq!(expected, Blinker::<usize>::new().count_all(&stones, blinks)?);
+        assert_eq!(expected, report::<usize>(&stones, blinks)?.total);
+        Ok(())
+    }
+
+    #[test]
+    fn test_distinct() -> miette::Result<()> {
+        // "253000 1 7" -> "253 0 2024 14168" -> "512072 1 20 24 28676032"
+        let report = report::<u64>(&[125, 17], 3)?;
+
+        assert_eq!(vec![2, 3, 4, 5], report.distinct);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> miette::Result<()> {
+        let stones = parse_stones("125 17")?;
+
+        assert!(matches!(
+            Blinker::<u64>::new().count_all(&stones, 200),
+            Err(AocError::CountOverflow(200))
+        ));
+
+        let wide = Blinker::<u128>::new().count_all(&stones, 200)?;
+        assert_eq!(wide, report::<u128>(&stones, 200)?.total);
+        assert!(wide > u64::MAX as u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deep_count_doesnt_blow_the_stack() {
+        // far deeper than recursion would survive, it just runs out of u128 long before the end
+        let blinks = 1_000_000;
+
+        assert!(matches!(
+            Blinker::<u128>::new().count_all(&[125, 17], blinks),
+            Err(AocError::CountOverflow(b)) if b == blinks
+        ));
+    }
+
+    #[cfg(feature = "big-int")]
+    #[test]
+    fn test_big_int() -> miette::Result<()> {
+        let stones = parse_stones("125 17")?;
+
+        let small = Blinker::<u128>::new().count_all(&stones, 150)?;
+        let big = Blinker::<num_bigint::BigUint>::new().count_all(&stones, 150)?;
+
+        assert_eq!(num_bigint::BigUint::from(small), big);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_stone() {
+        let Err(AocError::Core(aoc_core::AocError::ParseError { span, .. })) = parse_stones("125 x") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((4, 1), (span.offset(), span.len()));
+    }
+}
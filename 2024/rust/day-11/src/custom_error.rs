@@ -1,9 +1,28 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("stone count overflowed after {0} blinks, try a wider count type")]
+    #[diagnostic(code(aoc::count_overflow))]
+    CountOverflow(usize),
+
+    #[error("stone {0} is too big to multiply by 2024")]
+    #[diagnostic(code(aoc::stone_overflow))]
+    StoneOverflow(u64),
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::CountOverflow(..) => ErrorKind::NoSolution,
+            Self::StoneOverflow(..) => ErrorKind::InvalidInput,
+        }
+    }
+}
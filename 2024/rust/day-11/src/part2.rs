@@ -1,39 +1,20 @@
-use crate::custom_error::AocError;
+use crate::{blinker::{parse_stones, Blinker}, custom_error::AocError};
 
-use std::collections::HashMap;
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    let stones = parse_stones(input)?;
 
-fn run(stones: HashMap<usize, usize>) -> HashMap<usize, usize> {
-    let mut new_stones: HashMap<usize, usize> = HashMap::new();
-    
-    for (stone, count) in stones {
-        let length = stone.to_string().len();
-        
-        if stone == 0 {
-            *new_stones.entry(1).or_default() += count;
-        } else if length % 2 == 0 {
-            let divisor = 10_usize.pow((length / 2) as u32);
-            *new_stones.entry(stone / divisor).or_default() += count;
-            *new_stones.entry(stone % divisor).or_default() += count;
-        } else {
-            *new_stones.entry(stone * 2024).or_default() += count;
-        }
-    }
-    
-    new_stones
+    Ok(Blinker::<u128>::new().count_all(&stones, 75)?.to_string())
 }
 
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut stones: HashMap<usize, usize> = input
-        .split_whitespace()
-        .map(|s| s.parse::<usize>().unwrap())
-        .fold(HashMap::new(), |mut acc, stone| {
-            *acc.entry(stone).or_default() += 1;
-            acc
-        });
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for _ in 0..75 {
-        stones = run(stones);
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "125 17";
+        assert_eq!("65601038650482", process(input)?);
+        Ok(())
     }
-    
-    Ok(stones.values().sum::<usize>().to_string())
-}
\ No newline at end of file
+}
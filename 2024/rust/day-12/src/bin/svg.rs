@@ -0,0 +1,14 @@
+use day_12::regions::Garden;
+use miette::{Context, IntoDiagnostic};
+
+/// Writes the garden from the input, or the file given as the first argument, to garden.svg
+fn main() -> miette::Result<()> {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path).into_diagnostic()?,
+        None => include_str!("../../input1.txt").to_string(),
+    };
+
+    let svg = Garden::parse(&input).context("parse garden")?.svg(10);
+    std::fs::write("garden.svg", svg).into_diagnostic()?;
+    Ok(())
+}
@@ -1,9 +1,24 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("row {0} of the garden is not as wide as the first one")]
+    #[diagnostic(code(aoc::uneven_row))]
+    UnevenRow(usize),
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::UnevenRow(..) => ErrorKind::InvalidInput,
+        }
+    }
+}
+
@@ -0,0 +1,331 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use glam::IVec2;
+
+use crate::custom_error::AocError;
+
+/// Up, Right, Down, Left, so turning right is `(dir + 1) % 4`
+const DIRECTIONS: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// One closed boundary loop through the corners between cells.
+///
+/// Loops keep the region on their right, so in screen coordinates an outer
+/// boundary runs clockwise and a hole runs anticlockwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    /// only the corners, where the boundary turns
+    pub vertices: Vec<IVec2>,
+    pub hole: bool,
+}
+
+impl Polygon {
+    fn new(points: Vec<IVec2>) -> Self {
+        let n = points.len();
+        let vertices: Vec<IVec2> = (0..n)
+            .filter(|&i| {
+                let (prev, here, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+                (here - prev).perp_dot(next - here) != 0
+            })
+            .map(|i| points[i])
+            .collect();
+
+        let twice_area: i32 = (0..vertices.len())
+            .map(|i| vertices[i].perp_dot(vertices[(i + 1) % vertices.len()]))
+            .sum();
+
+        Self { vertices, hole: twice_area < 0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub plant: char,
+    /// in reading order
+    pub cells: Vec<IVec2>,
+    pub perimeter: usize,
+    /// outer boundary first, then any holes
+    pub polygons: Vec<Polygon>,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Every corner of every boundary starts a new side
+    pub fn sides(&self) -> usize {
+        self.polygons.iter().map(|polygon| polygon.vertices.len()).sum()
+    }
+
+    pub fn price(&self) -> usize {
+        self.area() * self.perimeter
+    }
+
+    pub fn discounted_price(&self) -> usize {
+        self.area() * self.sides()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Garden {
+    width: usize,
+    height: usize,
+    plants: Vec<char>,
+}
+
+impl Garden {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        let rows = input.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        if let Some(row) = rows.iter().position(|row| row.chars().count() != width) {
+            return Err(AocError::UnevenRow(row + 1));
+        }
+
+        Ok(Self { width, height: rows.len(), plants: rows.iter().flat_map(|row| row.chars()).collect() })
+    }
+
+    fn plant(&self, pos: IVec2) -> Option<char> {
+        (pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height)
+            .then(|| self.plants[pos.y as usize * self.width + pos.x as usize])
+    }
+
+    /// Every region in reading order of its first cell
+    pub fn regions(&self) -> Vec<Region> {
+        let mut seen = vec![false; self.plants.len()];
+        let mut regions = Vec::new();
+
+        for idx in 0..self.plants.len() {
+            if !seen[idx] {
+                let start = IVec2::new((idx % self.width) as i32, (idx / self.width) as i32);
+                regions.push(self.explore(start, &mut seen));
+            }
+        }
+
+        regions
+    }
+
+    fn explore(&self, start: IVec2, seen: &mut [bool]) -> Region {
+        let plant = self.plants[start.y as usize * self.width + start.x as usize];
+        let mut stack = vec![start];
+        let mut cells = Vec::new();
+        // boundary edges as (y, x, dir) of the corner they start from, so they sort in reading order
+        let mut edges = BTreeSet::new();
+
+        seen[start.y as usize * self.width + start.x as usize] = true;
+        while let Some(pos) = stack.pop() {
+            cells.push(pos);
+
+            for (dir, &delta) in DIRECTIONS.iter().enumerate() {
+                let next = pos + delta;
+
+                if self.plant(next) != Some(plant) {
+                    let from = Self::edge_start(pos, dir);
+                    edges.insert((from.y, from.x, dir));
+                    continue;
+                }
+
+                let idx = next.y as usize * self.width + next.x as usize;
+                if !seen[idx] {
+                    seen[idx] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        cells.sort_by_key(|pos| (pos.y, pos.x));
+        let perimeter = edges.len();
+        let polygons = Self::trace(edges);
+
+        Region { plant, cells, perimeter, polygons }
+    }
+
+    /// The corner a clockwise walk around `cell` is at when it starts along the side facing `dir`
+    fn edge_start(cell: IVec2, dir: usize) -> IVec2 {
+        cell + [IVec2::ZERO, IVec2::X, IVec2::ONE, IVec2::Y][dir]
+    }
+
+    /// Joins boundary edges into closed loops.
+    ///
+    /// Where two cells only touch diagonally a corner has two ways out. Turning left
+    /// first, away from the region, wraps each loop around a single outside area, so
+    /// holes that meet at a corner still come out as separate polygons.
+    fn trace(mut edges: BTreeSet<(i32, i32, usize)>) -> Vec<Polygon> {
+        let mut polygons = Vec::new();
+
+        while let Some((y, x, first)) = edges.pop_first() {
+            // the side facing `dir` runs along the next direction clockwise
+            let walk = |dir: usize| DIRECTIONS[(dir + 1) % 4];
+
+            let mut points = vec![IVec2::new(x, y)];
+            let mut pos = IVec2::new(x, y) + walk(first);
+            let mut dir = first;
+
+            while pos != points[0] {
+                points.push(pos);
+                dir = [(dir + 3) % 4, dir, (dir + 1) % 4]
+                    .into_iter()
+                    .find(|&next| edges.remove(&(pos.y, pos.x, next)))
+                    .expect("boundary loops are closed");
+                pos += walk(dir);
+            }
+
+            polygons.push(Polygon::new(points));
+        }
+
+        // the first edge in reading order is always on the outside
+        polygons.sort_by_key(|polygon| polygon.hole);
+        polygons
+    }
+
+    /// The whole garden with every region as a filled polygon, holes cut out,
+    /// and its numbers in a tooltip. `scale` is the size of a cell in pixels.
+    pub fn svg(&self, scale: u32) -> String {
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            self.width as u32 * scale,
+            self.height as u32 * scale,
+            self.width,
+            self.height,
+        );
+        svg.push('\n');
+
+        for region in self.regions() {
+            let mut path = String::new();
+            for polygon in &region.polygons {
+                for (i, vertex) in polygon.vertices.iter().enumerate() {
+                    let command = if i == 0 { 'M' } else { 'L' };
+                    write!(path, "{command}{} {} ", vertex.x, vertex.y).unwrap();
+                }
+                path.push('Z');
+            }
+
+            writeln!(
+                svg,
+                r#"<path d="{path}" fill="{}" fill-rule="evenodd" stroke="black" stroke-width="0.05"><title>{}: area {}, perimeter {}, sides {}</title></path>"#,
+                colour(region.plant),
+                region.plant,
+                region.area(),
+                region.perimeter,
+                region.sides(),
+            )
+            .unwrap();
+        }
+
+        svg + "</svg>\n"
+    }
+}
+
+/// A stable colour per plant, spread around the hue wheel
+fn colour(plant: char) -> String {
+    format!("hsl({}, 70%, 60%)", (plant as u32 * 137) % 360)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+    #[test]
+    fn test_regions() -> miette::Result<()> {
+        let regions = Garden::parse(EXAMPLE)?.regions();
+
+        let summary = regions
+            .iter()
+            .map(|region| (region.plant, region.area(), region.perimeter, region.sides()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ('R', 12, 18, 10),
+                ('I', 4, 8, 4),
+                ('C', 14, 28, 22),
+                ('F', 10, 18, 12),
+                ('V', 13, 20, 10),
+                ('J', 11, 20, 12),
+                ('C', 1, 4, 4),
+                ('E', 13, 18, 8),
+                ('I', 14, 22, 16),
+                ('M', 5, 12, 6),
+                ('S', 3, 8, 6),
+            ],
+            summary
+        );
+        assert_eq!(1930, regions.iter().map(Region::price).sum::<usize>());
+        assert_eq!(1206, regions.iter().map(Region::discounted_price).sum::<usize>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_enclave() -> miette::Result<()> {
+        let regions = Garden::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO")?.regions();
+        let outer = &regions[0];
+
+        assert_eq!(5, regions.len());
+        assert_eq!(5, outer.polygons.len());
+        assert!(!outer.polygons[0].hole);
+        assert!(outer.polygons[1..].iter().all(|polygon| polygon.hole));
+        assert_eq!(
+            vec![IVec2::new(0, 0), IVec2::new(5, 0), IVec2::new(5, 5), IVec2::new(0, 5)],
+            outer.polygons[0].vertices
+        );
+        assert_eq!(
+            vec![IVec2::new(1, 1), IVec2::new(1, 2), IVec2::new(2, 2), IVec2::new(2, 1)],
+            outer.polygons[1].vertices
+        );
+        assert_eq!(20, outer.sides());
+        assert_eq!(36, outer.perimeter);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagonal_touch() -> miette::Result<()> {
+        let garden = Garden::parse("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA")?;
+        let regions = garden.regions();
+
+        // the two B holes meet at a single corner, which splits them into separate loops
+        assert_eq!(3, regions[0].polygons.len());
+        assert_eq!(12, regions[0].sides());
+        assert_eq!(368, regions.iter().map(Region::discounted_price).sum::<usize>());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("AAAA\nBBCD\nBBCC\nEEEC", 140, 80)]
+    #[case("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE", 692, 236)]
+    fn test_prices(#[case] input: &str, #[case] price: usize, #[case] discounted: usize) -> miette::Result<()> {
+        let regions = Garden::parse(input)?.regions();
+
+        assert_eq!(price, regions.iter().map(Region::price).sum::<usize>());
+        assert_eq!(discounted, regions.iter().map(Region::discounted_price).sum::<usize>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg() -> miette::Result<()> {
+        let svg = Garden::parse("OOO\nOXO\nOOO")?.svg(10);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="30" viewBox="0 0 3 3">"#));
+        assert!(svg.contains(r#"d="M0 0 L3 0 L3 3 L0 3 ZM1 1 L1 2 L2 2 L2 1 Z""#));
+        assert!(svg.contains("<title>X: area 1, perimeter 4, sides 4</title>"));
+        assert_eq!(2, svg.matches("<path").count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_uneven_rows() {
+        assert!(matches!(Garden::parse("AAA\nAA"), Err(AocError::UnevenRow(2))));
+    }
+}
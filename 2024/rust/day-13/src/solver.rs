@@ -0,0 +1,217 @@
+use aoc_math::{diophantine, div_ceil, div_floor, solve_2x2};
+use aoc_parse::{parse_input, sections};
+use glam::IVec2;
+use nom::{character::complete::line_ending, combinator::map, sequence::tuple};
+
+use crate::{parse_button, parse_prize, AocError};
+
+/// Pressing A costs 3 tokens, pressing B costs 1
+pub const COST_A: i128 = 3;
+pub const COST_B: i128 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClawMachine {
+    pub a: IVec2,
+    pub b: IVec2,
+    pub prize: IVec2,
+}
+
+/// What changes between the parts of the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// added to both coordinates of every prize
+    pub offset: i128,
+    /// maximum number of presses per button
+    pub max_presses: Option<i128>,
+}
+
+impl Rules {
+    pub const PART1: Rules = Rules { offset: 0, max_presses: Some(100) };
+    pub const PART2: Rules = Rules { offset: 10_000_000_000_000, max_presses: None };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Presses {
+    pub a: i128,
+    pub b: i128,
+}
+
+impl Presses {
+    pub fn tokens(&self) -> i128 {
+        self.a * COST_A + self.b * COST_B
+    }
+}
+
+pub fn parse_machines(input: &str) -> miette::Result<Vec<ClawMachine>, AocError> {
+    let machine = map(
+        tuple((parse_button, line_ending, parse_button, line_ending, parse_prize)),
+        |(a, _, b, _, prize)| ClawMachine { a, b, prize },
+    );
+
+    parse_input(input, sections(machine))
+}
+
+/// Narrows `lo..=hi` (None meaning unbounded) to the k satisfying `base + k * step >= min`, step != 0
+fn at_least(base: i128, step: i128, min: i128, lo: &mut Option<i128>, hi: &mut Option<i128>) {
+    if step > 0 {
+        let k = div_ceil(min - base, step);
+        *lo = Some(lo.map_or(k, |lo| lo.max(k)));
+    } else {
+        let k = div_floor(min - base, step);
+        *hi = Some(hi.map_or(k, |hi| hi.min(k)));
+    }
+}
+
+/// Cheapest non-negative (a, b) with `p * a + q * b = r`, each at most `max`
+fn solve_line(p: i128, q: i128, r: i128, max: Option<i128>) -> Option<Presses> {
+    let within = |presses: i128| presses >= 0 && max.is_none_or(|max| presses <= max);
+
+    match (p, q) {
+        (0, 0) => (r == 0).then_some(Presses { a: 0, b: 0 }),
+        (0, q) => (r % q == 0 && within(r / q)).then_some(Presses { a: 0, b: r / q }),
+        (p, 0) => (r % p == 0 && within(r / p)).then_some(Presses { a: r / p, b: 0 }),
+        (p, q) => {
+            // every solution is (a0 + k * da, b0 + k * db)
+            let solutions = diophantine(p, q, r)?;
+            let (a0, b0, da, db) = (solutions.x, solutions.y, solutions.dx, solutions.dy);
+
+            let (mut lo, mut hi) = (None, None);
+            at_least(a0, da, 0, &mut lo, &mut hi);
+            at_least(b0, db, 0, &mut lo, &mut hi);
+            if let Some(max) = max {
+                at_least(-a0, -da, -max, &mut lo, &mut hi);
+                at_least(-b0, -db, -max, &mut lo, &mut hi);
+            }
+
+            if let (Some(lo), Some(hi)) = (lo, hi) {
+                if lo > hi {
+                    return None;
+                }
+            }
+
+            // the cost is linear in k, so the cheapest solution sits on one end of the range.
+            // a and b can't both grow without bound while the cost shrinks, so that end exists.
+            let slope = da * COST_A + db * COST_B;
+            let k = if slope > 0 { lo.or(hi) } else { hi.or(lo) }.unwrap_or(0);
+
+            Some(Presses { a: a0 + k * da, b: b0 + k * db })
+        }
+    }
+}
+
+/// Cheapest way to reach the prize, handling colinear (zero determinant) buttons
+pub fn solve(machine: &ClawMachine, rules: &Rules) -> Option<Presses> {
+    let (a_x, a_y) = (machine.a.x as i128, machine.a.y as i128);
+    let (b_x, b_y) = (machine.b.x as i128, machine.b.y as i128);
+    let t_x = machine.prize.x as i128 + rules.offset;
+    let t_y = machine.prize.y as i128 + rules.offset;
+
+    let within = |presses: i128| presses >= 0 && rules.max_presses.is_none_or(|max| presses <= max);
+    let reaches = |presses: &Presses| {
+        a_x * presses.a + b_x * presses.b == t_x && a_y * presses.a + b_y * presses.b == t_y
+    };
+
+    if let Some([a, b]) = solve_2x2([[a_x, b_x], [a_y, b_y]], [t_x, t_y]) {
+        // only one solution, and it has to be whole presses
+        let presses = Presses { a: a.to_integer()?, b: b.to_integer()? };
+        return (within(presses.a) && within(presses.b)).then_some(presses);
+    }
+
+    // colinear buttons: solve along whichever axis they move on, every solution of
+    // that equation lands on the same point of the line so one check covers the other axis
+    let presses = if a_x != 0 || b_x != 0 {
+        solve_line(a_x, b_x, t_x, rules.max_presses)?
+    } else {
+        solve_line(a_y, b_y, t_y, rules.max_presses)?
+    };
+
+    reaches(&presses).then_some(presses)
+}
+
+pub fn total_tokens(input: &str, rules: &Rules) -> miette::Result<i128, AocError> {
+    Ok(parse_machines(input)?
+        .iter()
+        .filter_map(|machine| solve(machine, rules))
+        .map(|presses| presses.tokens())
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn machine(a: (i32, i32), b: (i32, i32), prize: (i32, i32)) -> ClawMachine {
+        ClawMachine { a: IVec2::from(a), b: IVec2::from(b), prize: IVec2::from(prize) }
+    }
+
+    #[rstest]
+    #[case(machine((94, 34), (22, 67), (8400, 5400)), Some(Presses { a: 80, b: 40 }))]
+    #[case(machine((26, 66), (67, 21), (12748, 12176)), None)]
+    #[case(machine((17, 86), (84, 37), (7870, 6450)), Some(Presses { a: 38, b: 86 }))]
+    #[case(machine((69, 23), (27, 71), (18641, 10279)), None)]
+    fn test_part1_examples(#[case] machine: ClawMachine, #[case] expected: Option<Presses>) {
+        assert_eq!(expected, solve(&machine, &Rules::PART1));
+    }
+
+    #[rstest]
+    // B is cheaper per unit of distance than A (1 token for 1 vs 3 for 2)
+    #[case(machine((2, 2), (1, 1), (10, 10)), None, Some(Presses { a: 0, b: 10 }))]
+    // A is cheaper per unit of distance (3 tokens for 4 vs 1 for 1)
+    #[case(machine((4, 4), (1, 1), (10, 10)), None, Some(Presses { a: 2, b: 2 }))]
+    // the press cap forces more of the pricier button
+    #[case(machine((2, 2), (1, 1), (10, 10)), Some(4), Some(Presses { a: 3, b: 4 }))]
+    #[case(machine((2, 2), (1, 1), (10, 10)), Some(2), None)]
+    // on the line but not reachable with those step sizes
+    #[case(machine((4, 6), (6, 9), (5, 7)), None, None)]
+    #[case(machine((4, 6), (6, 9), (14, 21)), None, Some(Presses { a: 2, b: 1 }))]
+    // off the line entirely
+    #[case(machine((1, 1), (2, 2), (3, 4)), None, None)]
+    // buttons that only move along y
+    #[case(machine((0, 3), (0, 5), (0, 13)), None, Some(Presses { a: 1, b: 2 }))]
+    // buttons that don't move the claw at all
+    #[case(machine((0, 0), (0, 0), (0, 0)), None, Some(Presses { a: 0, b: 0 }))]
+    #[case(machine((0, 0), (0, 0), (1, 0)), None, None)]
+    fn test_colinear(#[case] machine: ClawMachine, #[case] max_presses: Option<i128>, #[case] expected: Option<Presses>) {
+        assert_eq!(expected, solve(&machine, &Rules { offset: 0, max_presses }));
+    }
+
+    #[test]
+    fn test_colinear_is_cheapest() {
+        let machine = machine((3, 6), (5, 10), (97, 194));
+        let best = solve(&machine, &Rules { offset: 0, max_presses: None }).unwrap();
+
+        let brute = (0..=97_i128)
+            .flat_map(|a| (0..=97_i128).map(move |b| Presses { a, b }))
+            .filter(|p| 3 * p.a + 5 * p.b == 97)
+            .min_by_key(|p| p.tokens())
+            .unwrap();
+
+        assert_eq!(brute.tokens(), best.tokens());
+    }
+
+    #[test]
+    fn test_press_limit() {
+        let machine = machine((1, 0), (0, 1), (101, 5));
+
+        assert_eq!(None, solve(&machine, &Rules::PART1));
+        assert_eq!(Some(Presses { a: 101, b: 5 }), solve(&machine, &Rules { offset: 0, max_presses: None }));
+    }
+
+    #[test]
+    fn test_parse_error_points_at_the_typo() {
+        let input = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Z+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+";
+        let Err(AocError::ParseError { span, .. }) = parse_machines(input) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(input.find('Z'), Some(span.offset()));
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
+use aoc_parse::{ivec2, labelled, lines, parse_input};
 use glam::IVec2;
 use nom::{
-    bytes::complete::tag, character::complete::{i32 as parse_i32, space0}, sequence::{preceded, separated_pair, tuple}, IResult
+    character::complete::space0, combinator::map, sequence::separated_pair, IResult
 };
 
 use crate::AocError;
@@ -22,32 +23,11 @@ pub struct Robot {
 
 pub struct Robots(Vec<Robot>);
 
-fn parse_position(input: &str) -> IResult<&str, Position> {
-    let (input, coords) = preceded(
-        tag("p="),
-        separated_pair(parse_i32, tag(","), parse_i32)
-    )(input)?;
-    
-    Ok((input, Position(IVec2::new(coords.0, coords.1))))
-}
-
-fn parse_velocity(input: &str) -> IResult<&str, Velocity> {
-    let (input, coords) = preceded(
-        tag("v="),
-        separated_pair(parse_i32, tag(","), parse_i32)
-    )(input)?;
-    
-    Ok((input, Velocity(IVec2::new(coords.0, coords.1))))
-}
-
 fn parse_robot(input: &str) -> IResult<&str, Robot> {
-    let (input, (position, _, velocity)) = tuple((
-        parse_position,
-        space0,  // This handles the space between position and velocity
-        parse_velocity
-    ))(input)?;
-
-    Ok((input, Robot { position, velocity }))
+    map(
+        separated_pair(labelled("p=", ivec2), space0, labelled("v=", ivec2)),
+        |(position, velocity)| Robot { position: Position(position), velocity: Velocity(velocity) },
+    )(input)
 }
 
 #[derive(Debug)]
@@ -129,15 +109,7 @@ impl std::ops::Deref for PhantomGrid {
 
 #[tracing::instrument]
 pub fn process(input: &str, dimensions: IVec2) -> miette::Result<String, AocError> {
-    let mut robots = input.lines()
-        .map(|line| {
-            let (_, robot) = parse_robot(line).unwrap();
-
-            // dbg!(robot)
-            robot
-        })
-        .collect::<Vec<_>>();
-    assert!(robots.len() > 0);
+    let mut robots = parse_input(input, lines(parse_robot))?;
 
     // dbg!(robots);
     // println!("Initial state:");
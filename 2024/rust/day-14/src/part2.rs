@@ -1,8 +1,10 @@
 use std::collections::HashSet;
 
+use aoc_parse::{ivec2, labelled, lines, parse_input};
+use aoc_sim::Simulation;
 use glam::IVec2;
 use nom::{
-    bytes::complete::tag, character::complete::{i32 as parse_i32, space0}, sequence::{preceded, separated_pair, tuple}, IResult
+    character::complete::space0, combinator::map, sequence::separated_pair, IResult
 };
 
 use crate::AocError;
@@ -22,38 +24,17 @@ pub struct Robot {
 
 pub struct Robots(Vec<Robot>);
 
-fn parse_position(input: &str) -> IResult<&str, Position> {
-    let (input, coords) = preceded(
-        tag("p="),
-        separated_pair(parse_i32, tag(","), parse_i32)
-    )(input)?;
-    
-    Ok((input, Position(IVec2::new(coords.0, coords.1))))
-}
-
-fn parse_velocity(input: &str) -> IResult<&str, Velocity> {
-    let (input, coords) = preceded(
-        tag("v="),
-        separated_pair(parse_i32, tag(","), parse_i32)
-    )(input)?;
-    
-    Ok((input, Velocity(IVec2::new(coords.0, coords.1))))
-}
-
 fn parse_robot(input: &str) -> IResult<&str, Robot> {
-    let (input, (position, _, velocity)) = tuple((
-        parse_position,
-        space0,  // This handles the space between position and velocity
-        parse_velocity
-    ))(input)?;
-
-    Ok((input, Robot { position, velocity }))
+    map(
+        separated_pair(labelled("p=", ivec2), space0, labelled("v=", ivec2)),
+        |(position, velocity)| Robot { position: Position(position), velocity: Velocity(velocity) },
+    )(input)
 }
 
-fn has_no_overlaps(robots: &[Robot]) -> bool {
+fn has_no_overlaps(robots: &[IVec2]) -> bool {
     let mut positions = HashSet::new();
     // If we can't insert a position, it means there's already a robot there
-    robots.iter().all(|robot| positions.insert(robot.position.0))
+    robots.iter().all(|&robot| positions.insert(robot))
 }
 
 #[derive(Debug)]
@@ -135,39 +116,35 @@ impl std::ops::Deref for PhantomGrid {
 
 #[tracing::instrument]
 pub fn process(input: &str, dimensions: IVec2) -> miette::Result<String, AocError> {
-    let mut robots = input.lines()
-        .map(|line| {
-            let (_, robot) = parse_robot(line).unwrap();
-
-            // dbg!(robot)
-            robot
-        })
-        .collect::<Vec<_>>();
-    assert!(robots.len() > 0);
+    let robots = parse_input(input, lines(parse_robot))?;
 
     // dbg!(robots);
     // println!("Initial state:");
     // println!("{}", visualize_robots(&robots, dimensions.x, dimensions.y));
 
-    let mut grid = PhantomGrid::new(dimensions.x, dimensions.y);
-
-    let mut steps = 0;
-
-    for step in 0.. {
-        grid.step(&mut robots);
-        // println!("Next state:");
-        // println!("{}", visualize_robots(&robots, dimensions.x, dimensions.y));
-
-        if has_no_overlaps(&robots) {
-            println!("Found no overlaps at step: {}", step + 1);  // +1 because we start at 0
-            steps = step + 1;
-            break;
-        }
+    let grid = PhantomGrid::new(dimensions.x, dimensions.y);
+    let velocities = robots.iter().map(|robot| robot.velocity.0).collect::<Vec<_>>();
+    let start = robots.iter().map(|robot| robot.position.0).collect::<Vec<_>>();
+
+    // the robots all end up back where they started eventually, so if they haven't
+    // spread out by the time the positions repeat they never will
+    // only hashes are kept, ten thousand or so copies of every robot would add up
+    let mut simulation = Simulation::with_recording(start, |positions: &Vec<IVec2>| {
+        Some(positions.iter().zip(&velocities).map(|(&position, &velocity)| grid.wrap_position(position + velocity)).collect())
+    }, false);
+
+    // the robots start out however they like, the picture is the first step they move into
+    let steps = simulation.find_from(1, |positions| has_no_overlaps(positions)).ok_or_else(|| {
+        let period = simulation.cycle().map_or(0, |cycle| cycle.period);
+        AocError::NoSolution(format!("robots still overlap after a full cycle of {period} steps"))
+    })?;
+    tracing::debug!(steps, "found no overlaps");
+
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        let robots = simulation.state_at(steps).expect("found at this step");
+        tracing::debug!("final state:\n{}", visualize_robots(robots, dimensions.x, dimensions.y));
     }
 
-    println!("Final state:");
-    println!("{}", visualize_robots(&robots, dimensions.x, dimensions.y));
-
     Ok(steps.to_string())
 }
 
@@ -211,15 +188,33 @@ p=9,5 v=-3,-3";
         assert_eq!("1", process(input, grid_size)?);
         Ok(())
     }
+
+    #[test]
+    fn test_skips_the_start() {
+        // already spread out, then they meet in the middle before passing each other
+        let input = "p=0,0 v=1,0
+p=2,0 v=-1,0";
+
+        assert_eq!("2", process(input, IVec2::new(11, 7)).unwrap());
+    }
+
+    #[test]
+    fn test_never_spread_out() {
+        // moving together, so they share a tile forever
+        let input = "p=0,0 v=1,2
+p=0,0 v=1,2";
+
+        assert!(matches!(process(input, IVec2::new(11, 7)), Err(AocError::NoSolution(_))));
+    }
 }
 
 // a method on PhantomGrid?
-fn visualize_robots(robots: &[Robot], width: i32, height: i32) -> String {
+fn visualize_robots(robots: &[IVec2], width: i32, height: i32) -> String {
     let mut output = String::new();
     for y in 0..height {
         for x in 0..width {
             let count = robots.iter()
-                .filter(|r| r.position.0.x == x && r.position.0.y == y)
+                .filter(|r| r.x == x && r.y == y)
                 .count();
             
             output.push(if count == 0 {
@@ -1,3 +1,4 @@
+use aoc_core::testing::Numbers;
 use day_16::*;
 
 fn main() {
@@ -19,4 +20,58 @@ fn part2() {
         "../input2.txt",
     )))
     .unwrap();
-}
\ No newline at end of file
+}
+/// A 141x141 maze like the real input: a spanning tree carved out of the grid with
+/// every tenth remaining wall knocked through, so part 2 has ties to follow
+fn generated_input() -> String {
+    const SIZE: usize = 141;
+    let mut numbers = Numbers::new(0x2024);
+
+    let mut rows = vec![vec![b'#'; SIZE]; SIZE];
+    let mut stack = vec![(1, SIZE - 2)];
+    rows[SIZE - 2][1] = b'.';
+    while let Some(&(x, y)) = stack.last() {
+        let options = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .map(|(dx, dy)| ((x as i32 + dx) as usize, (y as i32 + dy) as usize))
+            .filter(|&(x, y)| (1..SIZE - 1).contains(&x) && (1..SIZE - 1).contains(&y) && rows[y][x] == b'#')
+            .collect::<Vec<_>>();
+
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = options[numbers.below(options.len())];
+        rows[(y + ny) / 2][(x + nx) / 2] = b'.';
+        rows[ny][nx] = b'.';
+        stack.push((nx, ny));
+    }
+
+    for y in 1..SIZE - 1 {
+        for x in 1..SIZE - 1 {
+            if rows[y][x] == b'#' && (x + y) % 2 == 1 && numbers.below(10) == 0 {
+                rows[y][x] = b'.';
+            }
+        }
+    }
+    rows[SIZE - 2][1] = b'S';
+    rows[1][SIZE - 2] = b'E';
+
+    rows.into_iter().map(|row| String::from_utf8(row).unwrap() + "\n").collect()
+}
+
+mod generated {
+    use super::*;
+
+    #[divan::bench]
+    fn part1(bencher: divan::Bencher) {
+        let input = generated_input();
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn part2(bencher: divan::Bencher) {
+        let input = generated_input();
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
+}
@@ -1,29 +1,11 @@
-use std::{cmp::Ordering, collections::{BinaryHeap, HashSet}};
+use std::collections::HashSet;
 
 use glam::IVec2;
 
 use crate::AocError;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Day16 {
-    position: IVec2,
-    direction: Direction,
-    cost: usize,
-}
-
-impl Ord for Day16 {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Reverse ordering for min-heap
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl PartialOrd for Day16 {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
+/// Where the reindeer is and which way it's facing
+type Reindeer = (IVec2, Direction);
 
 /// COL, ROW
 #[derive(Debug, PartialEq, Eq)]
@@ -88,6 +70,19 @@ impl Direction {
     }
 }
 
+/// Stepping forward costs 1, turning on the spot 1000
+fn moves(walls: &HashSet<IVec2>, (position, direction): Reindeer) -> impl Iterator<Item = (Reindeer, usize)> {
+    let ahead = position + direction.to_position();
+
+    [
+        (!walls.contains(&ahead)).then_some(((ahead, direction), 1)),
+        Some(((position, direction.turn_right()), 1000)),
+        Some(((position, direction.turn_left()), 1000)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
 
@@ -100,72 +95,17 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     let walls = input.match_indices("#")
         .map(|(idx, _)| grid.to_position(idx))
         .collect::<HashSet<_>>();
-    let mut start = grid.to_position(input.find("S").unwrap());
-    let mut end = grid.to_position(input.find("E").unwrap());
-    let mut direction = Direction::Right;
-
-    // dbg!(start, end);
-    // dbg!(start, end, walls);
-
-    let initial_state = Day16 {
-        position: start,
-        direction: Direction::Right,
-        cost: 0,
-    };
-
-    // Create priority queue for states to explore
-    let mut queue = BinaryHeap::new();
-    
-    // Track visited states (position + direction)
-    let mut visited = HashSet::new();
-
-    queue.push(initial_state);
-
-    while let Some(current) = queue.pop() {
-        // If we reached the end, return the cost
-        if current.position == end {
-            return Ok(current.cost.to_string());
-        }
-
-        // Skip if we've seen this state
-        let state_key = (current.position, current.direction);
-        if !visited.insert(state_key) {
-            continue;
-        }
-
-        // Generate next possible moves:
-        
-        // 1. Try moving forward
-        let next_pos = current.position + current.direction.to_position();
-        if !walls.contains(&next_pos) {
-            queue.push(Day16 {
-                cost: current.cost + 1,
-                position: next_pos,
-                direction: current.direction,
-            });
-        }
-
-        // 2. Try turning right (cost 1000)
-        let right_dir = current.direction.turn_right();
-        queue.push(Day16 {
-            cost: current.cost + 1000,
-            position: current.position,
-            direction: right_dir,
-        });
-
-        // 3. Try turning left (cost 1000)
-        let left_dir = current.direction.turn_left();
-        queue.push(Day16 {
-            cost: current.cost + 1000,
-            position: current.position,
-            direction: left_dir,
-        });
-    }
+    let start = grid.to_position(input.find("S").unwrap());
+    let end = grid.to_position(input.find("E").unwrap());
 
-    // dbg!(visited.len());
-    // dbg!(queue);
+    let (_, cost) = aoc_graph::dijkstra(
+        (start, Direction::Right),
+        |&reindeer| moves(&walls, reindeer),
+        |&(position, _)| position == end,
+    )
+    .ok_or_else(|| AocError::NoSolution("there's no way from S to E".to_string()))?;
 
-    Ok(visited.len().to_string())
+    Ok(cost.to_string())
 }
 
 #[cfg(test)]
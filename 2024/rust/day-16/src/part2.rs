@@ -1,30 +1,11 @@
-use std::{cmp::Ordering, collections::{BinaryHeap, HashMap, HashSet}};
+use std::collections::HashSet;
 
 use glam::IVec2;
 
 use crate::AocError;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Day16 {
-    position: IVec2,
-    direction: Direction,
-    cost: usize,
-    predecessor: Option<(IVec2, Direction)>,
-}
-
-impl Ord for Day16 {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Reverse ordering for min-heap
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl PartialOrd for Day16 {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
+/// Where the reindeer is and which way it's facing
+type Reindeer = (IVec2, Direction);
 
 /// COL, ROW
 #[derive(Debug, PartialEq, Eq)]
@@ -85,6 +66,19 @@ impl Direction {
     }
 }
 
+/// Stepping forward costs 1, turning on the spot 1000
+fn moves(walls: &HashSet<IVec2>, (position, direction): Reindeer) -> impl Iterator<Item = (Reindeer, usize)> {
+    let ahead = position + direction.to_position();
+
+    [
+        (!walls.contains(&ahead)).then_some(((ahead, direction), 1)),
+        Some(((position, direction.turn_right()), 1000)),
+        Some(((position, direction.turn_left()), 1000)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
 
@@ -100,149 +94,25 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     let start = grid.to_position(input.find("S").unwrap());
     let end = grid.to_position(input.find("E").unwrap());
 
-    // dbg!(start, end);
-    // dbg!(start, end, walls);
-
-    let initial_state = Day16 {
-        position: start,
-        direction: Direction::Right,
-        cost: 0,
-        predecessor: None
-    };
-
-    // Create priority queue for states to explore
-    let mut queue = BinaryHeap::new();
-    
-    // Track visited states (position + direction)
-    let mut visited = HashSet::new();
-
-    let mut state_costs: HashMap<(IVec2, Direction), (usize, Vec<Day16>)> = HashMap::new();
-
-    queue.push(initial_state);
-    let mut min_cost = usize::MAX;
-    let mut optimal_tiles = HashSet::new();
-    let mut path_visited = HashSet::new();
-
-    while let Some(current) = queue.pop() {
-        // If cost exceeds minimum, skip this path
-        if current.cost > min_cost {
-            continue;
-        }
-
-        let state_key = (current.position, current.direction);
-
-        // Check if we've seen this state
-        match state_costs.get(&state_key) {
-            Some((prev_cost, _)) if current.cost > *prev_cost => continue,
-            Some((prev_cost, paths)) if current.cost == *prev_cost => {
-                // Add this path to existing equal-cost paths
-                let mut new_paths = paths.clone();
-                new_paths.push(current.clone());
-                state_costs.insert(state_key, (current.cost, new_paths));
-            },
-            _ => {
-                // New best path to this state
-                state_costs.insert(state_key, (current.cost, vec![current.clone()]));
-            }
-        }
-
-        // If we reached the end, reconstruct the paths to return the cost
-        if current.position == end {
-            if current.cost < min_cost {
-                min_cost = current.cost;
-                optimal_tiles.clear();
-                path_visited.clear();
-                reconstruct_paths(
-                    &(current.position, current.direction),
-                    &state_costs,
-                    &mut path_visited,
-                    &mut optimal_tiles
-                );
-            } else if current.cost == min_cost {
-                reconstruct_paths(
-                    &(current.position, current.direction),
-                    &state_costs,
-                    &mut path_visited,
-                    &mut optimal_tiles
-                );
-            }
-        }
-
-        // Skip if we've seen this state
-        // let state_key = (current.position, current.direction);
-        if !visited.insert(state_key) {
-            continue;
-        }
-
-        // Generate next possible moves:
-        
-        // 1. Try moving forward
-        let next_pos = current.position + current.direction.to_position();
-        if !walls.contains(&next_pos) {
-            queue.push(Day16 {
-                cost: current.cost + 1,
-                position: next_pos,
-                direction: current.direction,
-                predecessor: Some((current.position, current.direction)),
-            });
-        }
-
-        // 2. Try turning right (cost 1000)
-        let right_dir = current.direction.turn_right();
-        queue.push(Day16 {
-            cost: current.cost + 1000,
-            position: current.position,
-            direction: right_dir,
-            predecessor: Some((current.position, current.direction)),
-        });
-
-        // 3. Try turning left (cost 1000)
-        let left_dir = current.direction.turn_left();
-        queue.push(Day16 {
-            cost: current.cost + 1000,
-            position: current.position,
-            direction: left_dir,
-            predecessor: Some((current.position, current.direction)),
-        });
+    // every cheapest way to E, whichever way the reindeer ends up facing
+    let optimal = aoc_graph::dijkstra_all(
+        (start, Direction::Right),
+        |&reindeer| moves(&walls, reindeer),
+        |&(position, _)| position == end,
+    );
+    if optimal.goal_cost().is_none() {
+        return Err(AocError::NoSolution("there's no way from S to E".to_string()));
     }
 
-    // dbg!(visited.len());
-    // dbg!(queue);
-
-    // panic!("halt");
+    let optimal_tiles = optimal
+        .on_paths(optimal.goals())
+        .into_iter()
+        .map(|&(position, _)| position)
+        .collect::<HashSet<_>>();
 
     Ok(optimal_tiles.len().to_string())
 }
 
-fn reconstruct_paths(
-    state: &(IVec2, Direction),
-    state_costs: &HashMap<(IVec2, Direction), (usize, Vec<Day16>)>,
-    visited: &mut HashSet<(IVec2, Direction)>,
-    optimal_tiles: &mut HashSet<IVec2>
-) {
-    // If we've visited this state (position + direction), stop
-    if !visited.insert(*state) {
-        return;
-    }
-    
-    // Add just the position to optimal tiles
-    optimal_tiles.insert(state.0);
-    
-    // For each state that led to this one
-    if let Some((_, states)) = state_costs.get(state) {
-        for prev_state in states {
-            if let Some(pred) = &prev_state.predecessor {
-                reconstruct_paths(
-                    pred,
-                    state_costs,
-                    visited,
-                    optimal_tiles
-                );
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,11 @@
 use std::i64;
 
+use aoc_parse::{blank_line, comma_separated, labelled, parse_input, signed, unsigned};
+use nom::{
+    character::complete::line_ending,
+    sequence::{separated_pair, terminated, tuple},
+};
+
 use crate::AocError;
 
 #[derive(Debug)]
@@ -11,31 +17,21 @@ struct MachineState {
 }
 
 impl MachineState {
-    fn from_str(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let lines: Vec<&str> = input.lines().collect();
-        
-        // Parse registers (assuming format "Register X: value")
-        let reg_a = lines[0].split(": ").nth(1)
-            .ok_or("Invalid register A format")?
-            .parse()?;
-        let reg_b = lines[1].split(": ").nth(1)
-            .ok_or("Invalid register B format")?
-            .parse()?;
-        let reg_c = lines[2].split(": ").nth(1)
-            .ok_or("Invalid register C format")?
-            .parse()?;
-            
-        // Parse program (assuming format "Program: 0,1,5,4,3,0")
-        let program = lines[4].split(": ").nth(1)
-            .ok_or("Invalid program format")?
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<Vec<u8>, _>>()?;
-            
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let registers = tuple((
+            terminated(labelled("Register A:", signed), line_ending),
+            terminated(labelled("Register B:", signed), line_ending),
+            labelled("Register C:", signed),
+        ));
+        let program = labelled("Program:", comma_separated(unsigned));
+
+        let ((register_a, register_b, register_c), program) =
+            parse_input(input, separated_pair(registers, blank_line, program))?;
+
         Ok(MachineState {
-            register_a: reg_a,
-            register_b: reg_b,
-            register_c: reg_c,
+            register_a,
+            register_b,
+            register_c,
             program,
         })
     }
@@ -195,7 +191,7 @@ struct Instruction {
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let machine = MachineState::from_str(input).unwrap();
+    let machine = MachineState::parse(input)?;
 
     Ok(find_initial_a(&machine.program, 0, (machine.program.len() - 1) as i32).to_string())
 }
@@ -211,7 +207,7 @@ Register B: 0
 Register C: 0
 
 Program: 0,3,5,4,3,0";
-        let machine = MachineState::from_str(input).unwrap();
+        let machine = MachineState::parse(input)?;
 
         assert_eq!(117_440, find_initial_a(&machine.program, 0, (machine.program.len() - 1) as i32));
         Ok(())
@@ -1,3 +1,4 @@
+use aoc_core::testing::Numbers;
 use day_18::*;
 
 fn main() {
@@ -19,4 +20,31 @@ fn part2() {
         "../input2.txt",
     )))
     .unwrap();
-}
\ No newline at end of file
+}
+/// Every cell of the 71x71 memory space except the corners, in a shuffled order,
+/// so the way out gets cut off a few hundred bytes in like the real input
+fn generated_input() -> String {
+    let mut cells = (0..71 * 71)
+        .map(|idx| (idx % 71, idx / 71))
+        .filter(|&cell| cell != (0, 0) && cell != (70, 70))
+        .collect::<Vec<_>>();
+    Numbers::new(0x2024).shuffle(&mut cells);
+
+    cells.into_iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
+
+mod generated {
+    use super::*;
+
+    #[divan::bench]
+    fn part1(bencher: divan::Bencher) {
+        let input = generated_input();
+        bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+    }
+
+    #[divan::bench]
+    fn part2(bencher: divan::Bencher) {
+        let input = generated_input();
+        bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+    }
+}
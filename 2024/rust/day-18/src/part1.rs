@@ -25,52 +25,24 @@ impl PhantomGrid {
     }
 }
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 use glam::IVec2;
 
-const ORTHOGONAL: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
-
-fn bfs(grid: &PhantomGrid, todo: &mut VecDeque<(IVec2, u32)>) -> u32 {
-    todo.clear();
-    let mut seen = HashSet::new();
-    
-    todo.push_back((IVec2::ZERO, 0));
-    seen.insert(IVec2::ZERO);
-
-    println!("Starting BFS with obstacles: {:?}", grid.0);
+use crate::AocError;
 
-    while let Some((position, cost)) = todo.pop_front() {
-        println!("At position {:?} with cost {}", position, cost);
-        
-        #[cfg(debug_assertions)]
-        let a = 6;
-
-        #[cfg(not(debug_assertions))]
-        let a = 70;
+const ORTHOGONAL: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
 
-        if position == IVec2::new(a, a) {
-            println!("Found path with cost {}", cost);
-            return cost;
-        }
+/// The open cells next to `position`, inside the bounds
+fn steps(grid: &PhantomGrid, position: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+    let (min, max) = grid.1;
 
-        for offset in ORTHOGONAL {
-            let next = position + offset;
-            if next.x >= 0 && next.x <= a && 
-               next.y >= 0 && next.y <= a && 
-               !grid.0.contains(&next) && 
-               !seen.contains(&next) {
-                println!("Adding next position: {:?}", next);
-                todo.push_back((next, cost + 1));
-                seen.insert(next);
-            }
-        }
-    }
-
-    println!("No path found!");
-    u32::MAX
+    ORTHOGONAL
+        .into_iter()
+        .map(move |offset| position + offset)
+        .filter(move |next| next.cmpge(min).all() && next.cmple(max).all() && !grid.0.contains(next))
 }
 
-pub fn process(input: &str) -> miette::Result<String, crate::AocError> {
+pub fn process(input: &str) -> miette::Result<String, AocError> {
     #[cfg(debug_assertions)]
     let space = IVec2::new(6, 6);
     #[cfg(debug_assertions)]
@@ -94,11 +66,11 @@ pub fn process(input: &str) -> miette::Result<String, crate::AocError> {
         .collect::<HashSet<_>>();
 
     let grid = PhantomGrid(obstacles, (IVec2::ZERO, space));
-    let mut todo: VecDeque<(IVec2, u32)> = VecDeque::new();
-    
-    let shortest_path = bfs(&grid, &mut todo);
-    
-    Ok(shortest_path.to_string())
+
+    let shortest = aoc_graph::bfs_len(IVec2::ZERO, |&position| steps(&grid, position), |&position| position == space)
+        .ok_or_else(|| AocError::NoSolution("the exit is cut off".to_string()))?;
+
+    Ok(shortest.to_string())
 }
 
 #[cfg(test)]
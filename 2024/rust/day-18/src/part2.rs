@@ -25,64 +25,32 @@ impl PhantomGrid {
     }
 }
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 use glam::IVec2;
 
-const ORTHOGONAL: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
-
-fn bfs(grid: &PhantomGrid, todo: &mut VecDeque<(IVec2, u32)>, id: usize) -> u32 {
-    todo.clear();
-    let mut seen = HashSet::new();
-    
-    todo.push_back((IVec2::ZERO, 0));
-    seen.insert(IVec2::ZERO);
-
-    #[cfg(debug_assertions)]
-    println!("Starting BFS with obstacles: {:?}", grid.0);
+use crate::AocError;
 
-    while let Some((position, cost)) = todo.pop_front() {
-        #[cfg(debug_assertions)]
-        println!("At position {:?} with cost {}", position, cost);
-        
-        #[cfg(debug_assertions)]
-        let a = 6;
-
-        #[cfg(not(debug_assertions))]
-        let a = 70;
-
-        if position == IVec2::new(a, a) {
-            #[cfg(debug_assertions)]
-            println!("Found path with cost {}", cost);
-            return cost;
-        }
+const ORTHOGONAL: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
 
-        for offset in ORTHOGONAL {
-            let next = position + offset;
-            if next.x >= 0 && next.x <= a && 
-               next.y >= 0 && next.y <= a && 
-               !grid.0.contains(&next) && 
-               !seen.contains(&next) {
-                #[cfg(debug_assertions)]
-                println!("Adding next position: {:?}", next);
-                todo.push_back((next, cost + 1));
-                seen.insert(next);
-            }
-        }
-    }
+/// The open cells next to `position`, inside the bounds
+fn steps(grid: &PhantomGrid, position: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+    let (min, max) = grid.1;
 
-    println!("No path found!");
-    u32::MAX
+    ORTHOGONAL
+        .into_iter()
+        .map(move |offset| position + offset)
+        .filter(move |next| next.cmpge(min).all() && next.cmple(max).all() && !grid.0.contains(next))
 }
 
-pub fn process(input: &str) -> miette::Result<String, crate::AocError> {
+pub fn process(input: &str) -> miette::Result<String, AocError> {
     #[cfg(debug_assertions)]
     let space = IVec2::new(6, 6);
     #[cfg(not(debug_assertions))]
     let space = IVec2::new(70, 70);
 
-    let mut obstacles = HashSet::new();
-    let mut todo = VecDeque::new();
-    
+    let mut grid = PhantomGrid(HashSet::new(), (IVec2::ZERO, space));
+    let mut way_out = HashSet::new();
+
     // Process obstacles one by one until we find the blocker
     for line in input.lines() {
         let parts = line.split(',').collect::<Vec<_>>();
@@ -92,16 +60,25 @@ pub fn process(input: &str) -> miette::Result<String, crate::AocError> {
         );
         
         // Add this obstacle
-        obstacles.insert(pos);
-        let grid = PhantomGrid(obstacles.clone(), (IVec2::ZERO, space));
+        grid.0.insert(pos);
+
+        // The last way out still works unless this landed on it
+        if !way_out.is_empty() && !way_out.contains(&pos) {
+            continue;
+        }
         
-        // Try to find path with current obstacles
-        let path_length = bfs(&grid, &mut todo, 0);
+        // Try to find path with current obstacles, from both corners at once
+        let path = aoc_graph::bidirectional_bfs(
+            IVec2::ZERO,
+            space,
+            |&position| steps(&grid, position),
+            |&position| steps(&grid, position),
+        );
         
-        // If no path exists, we found our blocker
-        if path_length == u32::MAX {
-            // Return the coordinates of the blocking position
-            return Ok(format!("{},{}", pos.x, pos.y));
+        match path {
+            Some(path) => way_out = path.into_iter().collect(),
+            // If no path exists, we found our blocker
+            None => return Ok(format!("{},{}", pos.x, pos.y)),
         }
     }
 
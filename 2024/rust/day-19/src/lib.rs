@@ -1,12 +1,32 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("expected towel patterns, a blank line and then the designs")]
+    #[diagnostic(code(aoc::missing_designs))]
+    MissingDesigns,
+
+    #[error("more ways to arrange {0:?} than a u128 can count")]
+    #[diagnostic(code(aoc::too_many_arrangements))]
+    TooManyArrangements(String),
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::MissingDesigns => ErrorKind::InvalidInput,
+            Self::TooManyArrangements(..) => ErrorKind::NoSolution,
+        }
+    }
 }
 
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod towels;
\ No newline at end of file
@@ -1,39 +1,13 @@
-use itertools::Itertools;
-
-use crate::AocError;
-
-use std::collections::HashSet;
-
-fn count_ways(patterns: &HashSet<String>, target: &str) -> usize {
-    let n = target.len();
-    let mut dp = vec![0; n + 1];
-    dp[0] = 1;
-
-    for i in 1..=n {
-        for pattern in patterns {
-            let pattern_len = pattern.len();
-            if i >= pattern_len && &target[i - pattern_len..i] == pattern {
-                dp[i] += dp[i - pattern_len];
-            }
-        }
-    }
-
-    dp[n]
-}
+use crate::{towels::parse, AocError};
 
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (patterns_str, lines) = input.split("\n\n").collect_tuple().unwrap();
-
-    let patterns: HashSet<String> = patterns_str
-        .split(',') 
-        .map(|s| s.trim().to_string())
-        .collect();
+    let (matcher, designs) = parse(input)?;
 
-    let output = lines
-        .lines()
-        .map(|s| s.trim().to_string())
-        .map(|line| count_ways(&patterns, &line))
-        .sum::<usize>();
+    let output = designs.into_iter().try_fold(0_u128, |total, design| {
+        total
+            .checked_add(matcher.count_arrangements(design)?)
+            .ok_or_else(|| AocError::TooManyArrangements(design.to_string()))
+    })?;
 
     Ok(output.to_string())
 }
@@ -0,0 +1,239 @@
+use itertools::Itertools;
+
+use crate::AocError;
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    /// (next stripe colour, node index), at most a handful so a scan beats hashing
+    children: Vec<(u8, usize)>,
+    /// a towel pattern ends here
+    terminal: bool,
+}
+
+/// Trie over the available towel patterns
+#[derive(Debug, Clone)]
+pub struct TowelMatcher {
+    nodes: Vec<Node>,
+}
+
+impl TowelMatcher {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut nodes = vec![Node::default()];
+
+        for pattern in patterns {
+            let mut current = 0;
+
+            for &stripe in pattern.as_bytes() {
+                current = match nodes[current].children.iter().find(|(s, _)| *s == stripe) {
+                    Some(&(_, next)) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[current].children.push((stripe, next));
+                        next
+                    }
+                };
+            }
+
+            nodes[current].terminal = true;
+        }
+
+        Self { nodes }
+    }
+
+    /// Lengths of every pattern that matches `design` at `start`, shortest first
+    pub fn prefixes<'a>(&'a self, design: &'a [u8], start: usize) -> impl Iterator<Item = usize> + 'a {
+        design[start..]
+            .iter()
+            .scan(0, |current, stripe| {
+                let (_, next) = self.nodes[*current].children.iter().find(|(s, _)| s == stripe)?;
+                *current = *next;
+                Some(*next)
+            })
+            .enumerate()
+            .filter(|&(_, node)| self.nodes[node].terminal)
+            .map(|(i, _)| i + 1)
+    }
+
+    /// ways[i] is the number of arrangements for `design[i..]`, ways[len] = 1.
+    /// `None` is more than a u128 holds, which still counts as possible.
+    fn ways(&self, design: &[u8]) -> Vec<Option<u128>> {
+        let mut ways = vec![Some(0); design.len() + 1];
+        ways[design.len()] = Some(1);
+
+        for start in (0..design.len()).rev() {
+            ways[start] = self
+                .prefixes(design, start)
+                .try_fold(0_u128, |total, len| total.checked_add(ways[start + len]?));
+        }
+
+        ways
+    }
+
+    pub fn count_arrangements(&self, design: &str) -> miette::Result<u128, AocError> {
+        self.ways(design.as_bytes())[0].ok_or_else(|| AocError::TooManyArrangements(design.to_string()))
+    }
+
+    pub fn is_possible(&self, design: &str) -> bool {
+        self.ways(design.as_bytes())[0] != Some(0)
+    }
+
+    /// Lazily lists every arrangement of `design`, use `.take(n)` to limit them
+    pub fn arrangements<'a>(&'a self, design: &'a str) -> Arrangements<'a> {
+        let completable = self.ways(design.as_bytes()).into_iter().map(|ways| ways != Some(0)).collect_vec();
+
+        let mut arrangements = Arrangements { matcher: self, design, completable, stack: Vec::new(), path: Vec::new() };
+        if arrangements.completable[0] {
+            arrangements.push_frame(0);
+        }
+
+        arrangements
+    }
+}
+
+#[derive(Debug)]
+struct Frame {
+    start: usize,
+    lengths: Vec<usize>,
+    next: usize,
+}
+
+/// Depth first walk over the arrangements of one design, skipping dead ends
+#[derive(Debug)]
+pub struct Arrangements<'a> {
+    matcher: &'a TowelMatcher,
+    design: &'a str,
+    completable: Vec<bool>,
+    stack: Vec<Frame>,
+    path: Vec<usize>,
+}
+
+impl Arrangements<'_> {
+    fn push_frame(&mut self, start: usize) {
+        let lengths = self
+            .matcher
+            .prefixes(self.design.as_bytes(), start)
+            .filter(|len| self.completable[start + len])
+            .collect();
+
+        self.stack.push(Frame { start, lengths, next: 0 });
+    }
+}
+
+impl<'a> Iterator for Arrangements<'a> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+
+            if frame.start == self.design.len() {
+                let design = self.design;
+                let arrangement = self
+                    .path
+                    .iter()
+                    .scan(0, |start, len| {
+                        let towel = &design[*start..*start + len];
+                        *start += len;
+                        Some(towel)
+                    })
+                    .collect();
+
+                self.stack.pop();
+                self.path.pop();
+                return Some(arrangement);
+            }
+
+            match frame.lengths.get(frame.next) {
+                Some(&len) => {
+                    frame.next += 1;
+                    let start = frame.start + len;
+                    self.path.push(len);
+                    self.push_frame(start);
+                }
+                None => {
+                    self.stack.pop();
+                    self.path.pop();
+                }
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> miette::Result<(TowelMatcher, Vec<&str>), AocError> {
+    let (patterns, designs) = input.split_once("\n\n").ok_or(AocError::MissingDesigns)?;
+
+    let matcher = TowelMatcher::new(patterns.split(',').map(str::trim).filter(|p| !p.is_empty()));
+    let designs = designs.lines().map(str::trim).filter(|d| !d.is_empty()).collect();
+
+    Ok((matcher, designs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const PATTERNS: &str = "r, wr, b, g, bwu, rb, gb, br";
+
+    #[rstest]
+    #[case("brwrr", 2)]
+    #[case("bggr", 1)]
+    #[case("gbbr", 4)]
+    #[case("rrbgbr", 6)]
+    #[case("ubwu", 0)]
+    #[case("bwurrg", 1)]
+    #[case("brgr", 2)]
+    #[case("bbrgwb", 0)]
+    fn test_count_arrangements(#[case] design: &str, #[case] expected: u128) {
+        let matcher = TowelMatcher::new(PATTERNS.split(", "));
+
+        assert_eq!(expected, matcher.count_arrangements(design).unwrap());
+        assert_eq!(expected as usize, matcher.arrangements(design).count());
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let matcher = TowelMatcher::new(PATTERNS.split(", "));
+
+        assert_eq!(
+            vec![vec!["g", "b", "b", "r"], vec!["g", "b", "br"], vec!["gb", "b", "r"], vec!["gb", "br"]],
+            matcher.arrangements("gbbr").collect_vec()
+        );
+        assert_eq!(vec![vec!["g", "b", "b", "r"], vec!["g", "b", "br"]], matcher.arrangements("gbbr").take(2).collect_vec());
+        assert_eq!(0, matcher.arrangements("ubwu").count());
+    }
+
+    #[test]
+    fn test_arrangements_beyond_u64() {
+        let matcher = TowelMatcher::new(["r", "rr"]);
+        let design = "r".repeat(150);
+
+        // tilings of a strip with 1 and 2 long pieces are fibonacci numbers
+        let (mut a, mut b) = (1_u128, 1_u128);
+        for _ in 0..150 {
+            (a, b) = (b, a + b);
+        }
+
+        assert_eq!(a, matcher.count_arrangements(&design).unwrap());
+        assert!(a > u64::MAX as u128);
+        assert_eq!(vec!["r"; 150], matcher.arrangements(&design).next().unwrap());
+    }
+
+    #[test]
+    fn test_too_many_arrangements() {
+        // fibonacci again, F(201) is well past u128
+        let matcher = TowelMatcher::new(["r", "rr"]);
+        let design = "r".repeat(200);
+
+        assert!(matches!(matcher.count_arrangements(&design), Err(AocError::TooManyArrangements(_))));
+        assert!(matcher.is_possible(&design));
+        assert_eq!(vec!["r"; 200], matcher.arrangements(&design).next().unwrap());
+    }
+
+    #[test]
+    fn test_missing_designs() {
+        assert!(matches!(parse("r, wr, b"), Err(AocError::MissingDesigns)));
+    }
+}
@@ -8,7 +8,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 
     let mut group = c.benchmark_group("day_20::part1");
     group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input))
+        b.iter(|| part1::process(input, 100))
     });
 
     group.finish();
@@ -19,7 +19,7 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
 
     let mut group = c.benchmark_group("day_20::part2");
     group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input))
+        b.iter(|| part2::process(input, 100))
     });
     group.finish();
 }
@@ -9,7 +9,7 @@ fn main() {
 fn part1() {
     part1::process(divan::black_box(include_str!(
         "../input1.txt",
-    )))
+    )), 100)
     .unwrap();
 }
 
@@ -17,6 +17,20 @@ fn part1() {
 fn part2() {
     part2::process(divan::black_box(include_str!(
         "../input2.txt",
-    )))
+    )), 100)
     .unwrap();
-}
\ No newline at end of file
+}
+
+#[divan::bench(args = [101, 501, 1001])]
+fn serpentine(size: usize) {
+    let track = cheats::Track::parse(&cheats::serpentine(size, size)).unwrap();
+
+    divan::black_box(track.count_cheats(20, 100));
+}
+
+#[divan::bench(args = [101, 501, 1001])]
+fn parse(bencher: divan::Bencher, size: usize) {
+    let input = cheats::serpentine(size, size);
+
+    bencher.bench(|| cheats::Track::parse(divan::black_box(&input)).unwrap());
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+
+use aoc_graph::UNREACHED;
+
+use crate::{AocError, Position, DIRECTIONS};
+
+/// The race track with the distance from the start stored for every cell
+#[derive(Debug, Clone)]
+pub struct Track {
+    width: usize,
+    height: usize,
+    /// row major, `UNREACHED` for walls and cells the program can't get to
+    dist: Vec<u32>,
+    /// every track cell, ordered by distance from the start
+    path: Vec<Position>,
+}
+
+impl Track {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let start = rows
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| row.iter().position(|&c| c == b'S').map(|x| Position::new(x as i32, y as i32)))
+            .ok_or(AocError::MissingStart)?;
+
+        let open = |pos: Position| rows[pos.y as usize].get(pos.x as usize).is_some_and(|&c| c != b'#');
+
+        let mut track = Self { width, height, dist: Vec::new(), path: Vec::new() };
+        // over a slice rather than the array by value, which the optimiser
+        // unrolls about as well as a hand written loop
+        let reach = aoc_graph::bfs_reach_dense(start, width * height, |&pos| track.index(pos), |&pos| {
+            DIRECTIONS.iter().map(move |&dir| pos + dir).filter(|&next| track.in_bounds(next) && open(next))
+        });
+
+        track.path = reach.order;
+        track.dist = reach.steps;
+
+        Ok(track)
+    }
+
+    fn in_bounds(&self, pos: Position) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index(&self, pos: Position) -> usize {
+        pos.y as usize * self.width + pos.x as usize
+    }
+
+    /// Picoseconds from the start, `None` for walls
+    pub fn distance(&self, pos: Position) -> Option<u32> {
+        self.in_bounds(pos)
+            .then(|| self.dist[self.index(pos)])
+            .filter(|&dist| dist != UNREACHED)
+    }
+
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// How many cheats save each amount of time, only counting savings of at least `min_saving`.
+    ///
+    /// A cheat lasting up to `duration` picoseconds can end anywhere within that manhattan
+    /// distance, so only the diamond around each track cell is scanned.
+    pub fn savings(&self, duration: i32, min_saving: u32) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for &from in &self.path {
+            let start = self.dist[self.index(from)];
+
+            for dy in -duration..=duration {
+                let reach = duration - dy.abs();
+
+                for dx in -reach..=reach {
+                    let to = from + Position::new(dx, dy);
+                    if !self.in_bounds(to) {
+                        continue;
+                    }
+
+                    let end = self.dist[self.index(to)];
+                    let cost = (dx.abs() + dy.abs()) as u32;
+
+                    if end != UNREACHED && end > start + cost && end - start - cost >= min_saving {
+                        *histogram.entry(end - start - cost).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        histogram
+    }
+
+    pub fn count_cheats(&self, duration: i32, min_saving: u32) -> usize {
+        self.savings(duration, min_saving).values().sum()
+    }
+}
+
+/// A single winding track of roughly `width` x `height`, handy for stress testing.
+///
+/// Corridors run along every other row and alternate which end they connect on,
+/// so every wall between them can be cheated through.
+pub fn serpentine(width: usize, height: usize) -> String {
+    let width = width.max(3);
+    let corridors = (height.max(3) - 1) / 2;
+
+    let mut rows = vec![vec![b'#'; width]; corridors * 2 + 1];
+    for corridor in 0..corridors {
+        let y = corridor * 2 + 1;
+        rows[y][1..width - 1].fill(b'.');
+
+        if corridor + 1 < corridors {
+            let x = if corridor % 2 == 0 { width - 2 } else { 1 };
+            rows[y + 1][x] = b'.';
+        }
+    }
+
+    rows[1][1] = b'S';
+    let last = corridors * 2 - 1;
+    rows[last][if corridors % 2 == 1 { width - 2 } else { 1 }] = b'E';
+
+    rows.into_iter()
+        .map(|row| String::from_utf8(row).expect("only ascii"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    #[test]
+    fn test_two_picosecond_table() -> miette::Result<()> {
+        let track = Track::parse(EXAMPLE)?;
+
+        // the race normally takes 84 picoseconds
+        assert_eq!(84, track.len() - 1);
+        assert_eq!(
+            BTreeMap::from([(2, 14), (4, 14), (6, 2), (8, 4), (10, 2), (12, 3), (20, 1), (36, 1), (38, 1), (40, 1), (64, 1)]),
+            track.savings(2, 1)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_twenty_picosecond_table() -> miette::Result<()> {
+        let track = Track::parse(EXAMPLE)?;
+
+        assert_eq!(
+            BTreeMap::from([
+                (50, 32), (52, 31), (54, 29), (56, 39), (58, 25), (60, 23), (62, 20),
+                (64, 19), (66, 12), (68, 14), (70, 12), (72, 22), (74, 4), (76, 3),
+            ]),
+            track.savings(20, 50)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_serpentine() -> miette::Result<()> {
+        let track = Track::parse(&serpentine(301, 301))?;
+
+        // 150 corridors of 299 cells and 149 links between them
+        assert_eq!(150 * 299 + 149, track.len());
+
+        // cutting from (x, y) to (x, y + 2) skips the rest of one corridor and the start of
+        // the next one, saving 598 - 2x, and each of the 149 walls offers that once
+        let savings = track.savings(2, 1);
+        assert_eq!((2..=596).step_by(2).map(|saving| (saving, 149)).collect::<BTreeMap<_, _>>(), savings);
+        assert_eq!(149 * 298, track.count_cheats(2, 1));
+        assert!(track.count_cheats(20, 100) > track.count_cheats(2, 100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_start() {
+        assert!(matches!(Track::parse("###\n#.#\n###"), Err(AocError::MissingStart)));
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -116,9 +117,23 @@ impl<T> Something<T> {
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("the race track has no start (S)")]
+    #[diagnostic(code(aoc::missing_start))]
+    MissingStart,
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::MissingStart => ErrorKind::InvalidInput,
+        }
+    }
 }
 
+pub mod cheats;
 pub mod part1;
 pub mod part2;
\ No newline at end of file
@@ -1,8 +1,5 @@
 use std::collections::{HashMap, HashSet};
 
-use miette::Diagnostic;
-use thiserror::Error;
-
 pub type Position = glam::IVec2;
 
 pub fn get_all_paths(from: Position, to: Position, skip: Position) -> Vec<String> {
@@ -68,12 +65,7 @@ fn recurse(chars: &[char], idx: usize, result: &mut Vec<String>, seen: &mut Hash
     }
 }
 
-#[derive(Error, Diagnostic, Debug)]
-pub enum AocError {
-    #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
-}
+pub use aoc_core::AocError;
 
 pub mod part1;
 pub mod part2;
\ No newline at end of file
@@ -1,6 +1,3 @@
-use miette::Diagnostic;
-use thiserror::Error;
-
 /// To mix a value into the secret number, calculate the bitwise XOR of 
 /// the given value and the secret number. Then, the secret number becomes 
 /// the result of that operation.
@@ -36,12 +33,22 @@ pub fn calculate_next_secret(mut secret: usize) -> usize {
     secret
 }
 
-#[derive(Error, Diagnostic, Debug)]
-pub enum AocError {
-    #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
+/// [`calculate_next_secret`] for several buyers at once.
+///
+/// Secrets never exceed 24 bits, so u32 lanes with wrapping shifts give the same
+/// result once pruned, and the fixed width loop is easy for LLVM to vectorise.
+pub fn calculate_next_secrets<const LANES: usize>(secrets: &mut [u32; LANES]) {
+    const PRUNE: u32 = 16777216 - 1;
+
+    for secret in secrets.iter_mut() {
+        *secret = ((*secret << 6) ^ *secret) & PRUNE;
+        *secret = ((*secret >> 5) ^ *secret) & PRUNE;
+        *secret = ((*secret << 11) ^ *secret) & PRUNE;
+    }
 }
 
+pub use aoc_core::AocError;
+
+pub mod market;
 pub mod part1;
 pub mod part2;
\ No newline at end of file
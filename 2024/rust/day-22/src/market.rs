@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+
+use rayon::prelude::*;
+
+use crate::{calculate_next_secrets, AocError};
+
+/// Buyers whose secrets are generated side by side
+pub const LANES: usize = 8;
+
+/// Prices are single digits so each change is one of -9..=9
+const CHANGES: usize = 19;
+
+/// Above this many possible windows the dense tables stop paying for themselves
+const DENSE_LIMIT: usize = CHANGES.pow(5);
+
+pub fn parse_secrets(input: &str) -> miette::Result<Vec<u32>, AocError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse().map_err(|_| AocError::parse_at(input, line, format!("secret {line:?} is not a number"))))
+        .collect()
+}
+
+/// Every buyer's price (last digit of the secret), from the initial secret onwards
+pub fn prices(secrets: &[u32], steps: usize) -> Vec<Vec<u8>> {
+    secrets
+        .par_chunks(LANES)
+        .flat_map_iter(|chunk| {
+            let mut lanes = [0; LANES];
+            lanes[..chunk.len()].copy_from_slice(chunk);
+
+            let mut prices = vec![Vec::with_capacity(steps + 1); chunk.len()];
+            for step in 0..=steps {
+                if step > 0 {
+                    calculate_next_secrets(&mut lanes);
+                }
+
+                for (prices, secret) in prices.iter_mut().zip(lanes) {
+                    prices.push((secret % 10) as u8);
+                }
+            }
+
+            prices
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestSequence {
+    pub changes: Vec<i8>,
+    pub bananas: u64,
+}
+
+/// How the monkey negotiates: how many price changes it watches and how many
+/// new secrets each buyer makes in a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Market {
+    pub window: usize,
+    pub steps: usize,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Self { window: 4, steps: 2000 }
+    }
+}
+
+impl Market {
+    /// The window of changes that earns the most bananas across all buyers,
+    /// `None` if no buyer sees that many changes
+    pub fn best_sequence(&self, secrets: &[u32]) -> Option<BestSequence> {
+        if self.window == 0 || self.window > self.steps {
+            return None;
+        }
+
+        let prices = prices(secrets, self.steps);
+
+        match CHANGES.checked_pow(self.window as u32) {
+            Some(patterns) if patterns <= DENSE_LIMIT => self.best_dense(&prices, patterns),
+            _ => self.best_sparse(&prices),
+        }
+    }
+
+    /// Windows are packed into base 19 numbers, oldest change in the highest digit,
+    /// and totalled in flat arrays. `seen` remembers the last buyer that hit each
+    /// window so it never needs clearing. The buyers are split into one chunk per
+    /// thread, so each thread fills one pair of tables however rayon splits the work.
+    fn best_dense(&self, prices: &[Vec<u8>], patterns: usize) -> Option<BestSequence> {
+        let chunk = prices.len().div_ceil(rayon::current_num_threads()).max(1);
+
+        let totals = prices
+            .par_chunks(chunk)
+            .map(|buyers| {
+                let mut totals = vec![0_u32; patterns];
+                let mut seen = vec![u32::MAX; patterns];
+
+                for (buyer, prices) in buyers.iter().enumerate() {
+                    let mut index = 0;
+
+                    for (i, pair) in prices.windows(2).enumerate() {
+                        let change = (pair[1] as i8 - pair[0] as i8 + 9) as usize;
+                        index = (index * CHANGES + change) % patterns;
+
+                        if i + 1 >= self.window && seen[index] != buyer as u32 {
+                            seen[index] = buyer as u32;
+                            totals[index] += pair[1] as u32;
+                        }
+                    }
+                }
+
+                totals
+            })
+            .reduce_with(|mut a, b| {
+                a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                a
+            })?;
+
+        let (index, &bananas) = totals
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, total)| total)?;
+
+        let mut changes = vec![0; self.window];
+        let mut rest = index;
+        for change in changes.iter_mut().rev() {
+            *change = (rest % CHANGES) as i8 - 9;
+            rest /= CHANGES;
+        }
+
+        Some(BestSequence { changes, bananas: bananas as u64 })
+    }
+
+    /// Fallback for long windows, where a table of every possible window won't fit
+    fn best_sparse(&self, prices: &[Vec<u8>]) -> Option<BestSequence> {
+        let totals = prices
+            .par_iter()
+            .fold(HashMap::new, |mut totals: HashMap<Vec<i8>, u64>, prices| {
+                let changes = prices.windows(2).map(|pair| pair[1] as i8 - pair[0] as i8).collect::<Vec<_>>();
+                let mut seen = HashSet::new();
+
+                for (window, &price) in changes.windows(self.window).zip(&prices[self.window..]) {
+                    if seen.insert(window) {
+                        *totals.entry(window.to_vec()).or_default() += price as u64;
+                    }
+                }
+
+                totals
+            })
+            .reduce(HashMap::new, |mut a, b| {
+                for (window, bananas) in b {
+                    *a.entry(window).or_default() += bananas;
+                }
+                a
+            });
+
+        totals
+            .into_iter()
+            .max_by(|(a_changes, a), (b_changes, b)| a.cmp(b).then_with(|| b_changes.cmp(a_changes)))
+            .map(|(changes, bananas)| BestSequence { changes, bananas })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::calculate_next_secret;
+
+    /// first price seen after each window, summed over buyers, the slow way
+    fn brute_force(secrets: &[u32], market: &Market) -> u64 {
+        let mut totals: HashMap<Vec<i8>, u64> = HashMap::new();
+
+        for &secret in secrets {
+            let mut secret = secret as usize;
+            let mut prices = vec![(secret % 10) as i8];
+            for _ in 0..market.steps {
+                secret = calculate_next_secret(secret);
+                prices.push((secret % 10) as i8);
+            }
+
+            let changes = prices.windows(2).map(|pair| pair[1] - pair[0]).collect::<Vec<_>>();
+            let mut seen = HashSet::new();
+            for (i, window) in changes.windows(market.window).enumerate() {
+                if seen.insert(window.to_vec()) {
+                    *totals.entry(window.to_vec()).or_default() += prices[i + market.window] as u64;
+                }
+            }
+        }
+
+        totals.into_values().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_lanes_match_scalar() {
+        let mut lanes = [1, 10, 100, 2024, 123, 0, 16777215, 42];
+        let mut scalar = lanes.map(|secret| secret as usize);
+
+        for _ in 0..100 {
+            calculate_next_secrets(&mut lanes);
+            scalar = scalar.map(calculate_next_secret);
+            assert_eq!(scalar, lanes.map(|secret| secret as usize));
+        }
+    }
+
+    #[test]
+    fn test_prices() {
+        assert_eq!(vec![vec![3, 0, 6, 5, 4, 4, 6, 4, 4, 2]], prices(&[123], 9));
+    }
+
+    #[test]
+    fn test_example() {
+        let best = Market::default().best_sequence(&[1, 2, 3, 2024]).unwrap();
+
+        assert_eq!(BestSequence { changes: vec![-2, 1, -1, 3], bananas: 23 }, best);
+    }
+
+    #[test]
+    fn test_single_buyer() {
+        let best = Market { window: 4, steps: 9 }.best_sequence(&[123]).unwrap();
+
+        assert_eq!(BestSequence { changes: vec![-1, -1, 0, 2], bananas: 6 }, best);
+    }
+
+    #[rstest]
+    #[case(Market { window: 1, steps: 200 })]
+    #[case(Market { window: 2, steps: 200 })]
+    #[case(Market { window: 3, steps: 500 })]
+    #[case(Market { window: 5, steps: 500 })]
+    #[case(Market { window: 6, steps: 500 })]
+    #[case(Market { window: 9, steps: 100 })]
+    fn test_window_sizes(#[case] market: Market) {
+        let secrets = (1..=20).map(|n| n * 7919).collect::<Vec<_>>();
+        let best = market.best_sequence(&secrets).unwrap();
+
+        assert_eq!(market.window, best.changes.len());
+        assert_eq!(brute_force(&secrets, &market), best.bananas);
+    }
+
+    #[test]
+    fn test_window_too_long() {
+        assert_eq!(None, Market { window: 10, steps: 9 }.best_sequence(&[123]));
+    }
+
+    #[test]
+    fn test_invalid_secret() {
+        let Err(AocError::ParseError { span, .. }) = parse_secrets("1\ntwo") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((2, 3), (span.offset(), span.len()));
+    }
+}
@@ -2,8 +2,9 @@ use std::collections::BTreeMap;
 
 use crate::AocError;
 
+use aoc_parse::{blank_line, key_value, lines, parse_input, unsigned};
 use nom::{
-    branch::alt, bytes::complete::{tag, take_while1}, character::complete::{space0, space1}, combinator::map, multi::separated_list1, sequence::tuple, IResult
+    branch::alt, bytes::complete::{tag, take_while1}, character::complete::space1, combinator::map, sequence::{separated_pair, tuple}, IResult
 };
 
 #[derive(Debug, PartialEq)]
@@ -24,21 +25,16 @@ pub struct LogicGate {
 
 // #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (initial_values, connections) = input.split_once("\n\n").unwrap();
+    let (initial_values, mut pending_gates) = parse_input(
+        input,
+        separated_pair(lines(key_value(identifier, ":", unsigned::<u8>)), blank_line, lines(logic_line)),
+    )?;
 
-    let mut wires = initial_values.lines().fold(BTreeMap::new(), |mut map, next| {
-        let (key, value) = next.split_once(":").unwrap();
-        let v = value.trim().parse::<u8>().unwrap();
+    let mut wires = initial_values
+        .into_iter()
+        .map(|(wire, value)| (wire.to_string(), value == 1))
+        .collect::<BTreeMap<_, _>>();
 
-        map.insert(key.to_string(), v == 1);
-
-        map
-    });
-
-    let binding = parse_logic_gates(connections).unwrap();
-
-    let mut pending_gates = binding.1;
-    
     // Keep evaluating as long as we make progress
     let mut made_progress = true;
     while made_progress && !pending_gates.is_empty() {
@@ -110,18 +106,8 @@ fn operation(input: &str) -> IResult<&str, Operation> {
 // Parse a single line
 fn logic_line(input: &str) -> IResult<&str, LogicGate> {
     map(
-        tuple((
-            identifier,
-            space1,
-            operation,
-            space1,
-            identifier,
-            space0,
-            tag("->"),
-            space0,
-            identifier,
-        )),
-        |(input1, _, op, _, input2, _, _, _, output)| LogicGate {
+        key_value(tuple((identifier, space1, operation, space1, identifier)), "->", identifier),
+        |((input1, _, op, _, input2), output)| LogicGate {
             // input: format!("{} {:?} {}", input1, op, input2),
             left: input1.to_string(),
             right: input2.to_string(),
@@ -131,14 +117,6 @@ fn logic_line(input: &str) -> IResult<&str, LogicGate> {
     )(input)
 }
 
-// Parse multiple lines
-fn parse_logic_gates(input: &str) -> IResult<&str, Vec<LogicGate>> {
-    separated_list1(
-        tag("\n"),
-        logic_line,
-    )(input)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
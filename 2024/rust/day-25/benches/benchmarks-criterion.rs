@@ -14,19 +14,5 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
     group.finish();
 }
 
-fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
-
-    let mut group = c.benchmark_group("day_25::part2");
-    group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input))
-    });
-    group.finish();
-}
-
-criterion_group!(
-    benches,
-    criterion_benchmark_part1,
-    criterion_benchmark_part2
-);
-criterion_main!(benches);
\ No newline at end of file
+criterion_group!(benches, criterion_benchmark_part1);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+use aoc_core::testing::Numbers;
 use day_25::*;
 
 fn main() {
@@ -13,10 +14,30 @@ fn part1() {
     .unwrap();
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+/// `count` locks and as many keys, 5 x 7 like the puzzle
+fn generated_input(count: usize) -> String {
+    let mut numbers = Numbers::new(0x2025);
+    let mut height = move || numbers.below(6);
+
+    (0..count * 2)
+        .map(|index| {
+            let heights = [(); 5].map(|_| height());
+            (0..7)
+                .map(|row| {
+                    // distance from the filled edge, heights stop at 5 so the far row stays empty
+                    let reach = if index % 2 == 0 { row } else { 6 - row };
+                    heights.map(|h| if reach <= h { '#' } else { '.' }).iter().collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[divan::bench(args = [1_000, 10_000])]
+fn fit_count(bencher: divan::Bencher, count: usize) {
+    let schematics = schematics::Schematics::parse(&generated_input(count)).unwrap();
+
+    bencher.bench(|| divan::black_box(&schematics).fit_count());
+}
@@ -1,11 +1,26 @@
+use aoc_core::{Classify, ErrorKind};
 use miette::Diagnostic;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
 pub enum AocError {
     #[error(transparent)]
-    #[diagnostic(code(aoc::io_error))]
-    IoError(#[from] std::io::Error),
+    #[diagnostic(transparent)]
+    Core(#[from] aoc_core::AocError),
+
+    #[error("schematic {index} is not a lock or a key: {reason}")]
+    #[diagnostic(code(aoc::invalid_schematic))]
+    InvalidSchematic { index: usize, reason: String },
+}
+
+impl Classify for AocError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Core(error) => error.kind(),
+            Self::InvalidSchematic { .. } => ErrorKind::InvalidInput,
+        }
+    }
 }
 
-pub mod part1;
\ No newline at end of file
+pub mod part1;
+pub mod schematics;
\ No newline at end of file
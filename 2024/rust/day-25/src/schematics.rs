@@ -0,0 +1,325 @@
+use crate::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// filled top row, pins hanging down
+    Lock,
+    /// filled bottom row, teeth sticking up
+    Key,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub kind: Kind,
+    /// how far each column reaches, not counting the filled row it starts from
+    pub heights: Vec<u8>,
+}
+
+/// Every lock and key in the input, all with the same dimensions.
+///
+/// Each schematic is also kept as a bitmask of its filled cells, `height` bits per
+/// column, so a lock and key fit exactly when their masks share no bits.
+#[derive(Debug, Clone)]
+pub struct Schematics {
+    pub width: usize,
+    pub height: usize,
+    pub locks: Vec<Schematic>,
+    pub keys: Vec<Schematic>,
+    /// `u64`s per mask
+    words: usize,
+    lock_masks: Vec<u64>,
+    key_masks: Vec<u64>,
+}
+
+fn invalid(index: usize, reason: impl Into<String>) -> AocError {
+    AocError::InvalidSchematic {
+        index,
+        reason: reason.into(),
+    }
+}
+
+impl Schematics {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        let blocks = input
+            .split("\n\n")
+            .map(|block| {
+                block
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::as_bytes)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|rows| !rows.is_empty())
+            .collect::<Vec<_>>();
+
+        let Some(first) = blocks.first() else {
+            return Ok(Self::empty(0, 0));
+        };
+        let (width, height) = (first[0].len(), first.len());
+        if width == 0 || height < 2 {
+            return Err(invalid(0, "needs at least one column and two rows"));
+        }
+        if height > u8::MAX as usize {
+            return Err(invalid(0, "too tall"));
+        }
+
+        let mut schematics = Self::empty(width, height);
+        for (index, rows) in blocks.iter().enumerate() {
+            if rows.len() != height || rows.iter().any(|row| row.len() != width) {
+                return Err(invalid(
+                    index,
+                    format!("not {width} wide and {height} tall"),
+                ));
+            }
+            if let Some(&c) = rows
+                .iter()
+                .flat_map(|row| row.iter())
+                .find(|&&c| c != b'#' && c != b'.')
+            {
+                return Err(invalid(index, format!("unexpected {:?}", c as char)));
+            }
+
+            let kind = match (rows[0], rows[height - 1]) {
+                (top, bottom)
+                    if top.iter().all(|&c| c == b'#') && bottom.iter().all(|&c| c == b'.') =>
+                {
+                    Kind::Lock
+                }
+                (top, bottom)
+                    if top.iter().all(|&c| c == b'.') && bottom.iter().all(|&c| c == b'#') =>
+                {
+                    Kind::Key
+                }
+                _ => return Err(invalid(index, "needs one filled and one empty edge row")),
+            };
+
+            let heights = (0..width)
+                .map(|column| {
+                    // cells counted from the filled edge
+                    let cells = (0..height).map(|row| match kind {
+                        Kind::Lock => rows[row][column],
+                        Kind::Key => rows[height - 1 - row][column],
+                    });
+                    let filled = cells.clone().take_while(|&c| c == b'#').count();
+                    if cells.skip(filled).any(|c| c == b'#') {
+                        return Err(invalid(index, format!("column {column} has a gap")));
+                    }
+                    Ok((filled - 1) as u8)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            schematics.push(Schematic { kind, heights });
+        }
+
+        Ok(schematics)
+    }
+
+    fn empty(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            locks: Vec::new(),
+            keys: Vec::new(),
+            words: (width * height).div_ceil(64),
+            lock_masks: Vec::new(),
+            key_masks: Vec::new(),
+        }
+    }
+
+    /// Filled cells, bit `column * height + row` counting rows from the top
+    fn mask(&self, schematic: &Schematic) -> Vec<u64> {
+        let mut mask = vec![0; self.words];
+        for (column, &reach) in schematic.heights.iter().enumerate() {
+            let rows = reach as usize + 1;
+            let rows = match schematic.kind {
+                Kind::Lock => 0..rows,
+                Kind::Key => self.height - rows..self.height,
+            };
+            for row in rows {
+                let bit = column * self.height + row;
+                mask[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        mask
+    }
+
+    fn push(&mut self, schematic: Schematic) {
+        let mask = self.mask(&schematic);
+        match schematic.kind {
+            Kind::Lock => {
+                self.lock_masks.extend(mask);
+                self.locks.push(schematic);
+            }
+            Kind::Key => {
+                self.key_masks.extend(mask);
+                self.keys.push(schematic);
+            }
+        }
+    }
+
+    /// Whether the lock and key at these indices overlap nowhere
+    pub fn fits(&self, lock: usize, key: usize) -> bool {
+        let lock = &self.lock_masks[lock * self.words..][..self.words];
+        let key = &self.key_masks[key * self.words..][..self.words];
+
+        lock.iter().zip(key).all(|(l, k)| l & k == 0)
+    }
+
+    /// How many lock and key pairs fit together
+    pub fn fit_count(&self) -> usize {
+        if self.words == 1 {
+            // the usual case, a tight loop over plain integers
+            return self
+                .lock_masks
+                .iter()
+                .map(|lock| self.key_masks.iter().filter(|&key| lock & key == 0).count())
+                .sum();
+        }
+
+        (0..self.locks.len())
+            .map(|lock| {
+                (0..self.keys.len())
+                    .filter(|&key| self.fits(lock, key))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+
+    #[test]
+    fn test_heights() {
+        let schematics = Schematics::parse(EXAMPLE).unwrap();
+
+        assert_eq!((5, 7), (schematics.width, schematics.height));
+        assert_eq!(
+            vec![vec![0, 5, 3, 4, 3], vec![1, 2, 0, 5, 3]],
+            schematics
+                .locks
+                .iter()
+                .map(|lock| lock.heights.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                vec![5, 0, 2, 1, 3],
+                vec![4, 3, 4, 0, 2],
+                vec![3, 0, 2, 0, 1]
+            ],
+            schematics
+                .keys
+                .iter()
+                .map(|key| key.heights.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_fits_match_heights() {
+        let schematics = Schematics::parse(EXAMPLE).unwrap();
+
+        for (l, lock) in schematics.locks.iter().enumerate() {
+            for (k, key) in schematics.keys.iter().enumerate() {
+                let by_height = lock
+                    .heights
+                    .iter()
+                    .zip(&key.heights)
+                    .all(|(a, b)| (a + b) as usize <= schematics.height - 2);
+                assert_eq!(by_height, schematics.fits(l, k));
+            }
+        }
+        assert_eq!(3, schematics.fit_count());
+    }
+
+    #[test]
+    fn test_small_and_large() {
+        let small = Schematics::parse("##\n#.\n..\n\n..\n.#\n##\n\n..\n#.\n##").unwrap();
+        assert_eq!(vec![1, 0], small.locks[0].heights);
+        assert_eq!(1, small.fit_count());
+
+        // 20 x 10 needs more than one word per mask
+        let empty = ".".repeat(20);
+        let full = "#".repeat(20);
+        let pin = format!("{}#", ".".repeat(19));
+
+        let lock = [full.as_str(), &pin].into_iter().chain([empty.as_str(); 8]);
+        let key = [empty.as_str(); 9].into_iter().chain([full.as_str()]);
+        let tall_key = [empty.as_str()]
+            .into_iter()
+            .chain([pin.as_str(); 8])
+            .chain([full.as_str()]);
+        let input = [lock.collect::<Vec<_>>(), key.collect(), tall_key.collect()]
+            .map(|rows| rows.join("\n"))
+            .join("\n\n");
+
+        let large = Schematics::parse(&input).unwrap();
+        assert_eq!(1, large.locks[0].heights[19]);
+        assert_eq!(vec![0; 20], large.keys[0].heights);
+        assert_eq!(8, large.keys[1].heights[19]);
+        assert!(large.fits(0, 0));
+        assert!(!large.fits(0, 1));
+        assert_eq!(1, large.fit_count());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let cases = [
+            ("###\n...\n\n##\n..", 1),
+            ("###\n#..\n", 0),
+            ("###\n.#.\n#..\n...", 0),
+            ("###\n.x.\n...", 0),
+            ("#.#\n...\n...", 0),
+        ];
+
+        for (input, index) in cases {
+            assert!(
+                matches!(Schematics::parse(input), Err(AocError::InvalidSchematic { index: i, .. }) if i == index),
+                "{input:?}"
+            );
+        }
+    }
+}